@@ -1,155 +1,23 @@
-use advent_of_code::solve;
+use advent_of_code::{PuzzleInput, solutions};
 use criterion::{Criterion, criterion_group, criterion_main};
 
-macro_rules! bench {
-    ($function:ident) => {
-        fn $function(c: &mut Criterion) {
-            let mut year_day_part = stringify!($function)
-                .strip_prefix("benchmark_")
-                .unwrap()
-                .split("_");
-            let year = year_day_part.next().unwrap().parse().unwrap();
-            let day = year_day_part.next().unwrap().parse().unwrap();
-            let part = year_day_part.next().unwrap().parse().unwrap();
-            let input = std::fs::read_to_string(&format!("input/{}/{:02}.txt", year, day)).unwrap();
-            c.bench_function(stringify!($function), |b| {
-                b.iter(|| {
-                    solve(
-                        std::hint::black_box(year),
-                        std::hint::black_box(day),
-                        std::hint::black_box(part),
-                        std::hint::black_box(input.clone()),
-                    )
-                })
-            });
-        }
-    };
+fn benchmark(c: &mut Criterion) {
+    for solution in solutions() {
+        let path = format!("input/{}/{:02}.txt", solution.year, solution.day);
+        let Ok(input) = std::fs::read_to_string(&path) else {
+            eprintln!("Skipping {path} (not found)");
+            continue;
+        };
+        let input = PuzzleInput::from(input);
+        let name = format!(
+            "benchmark_{}_{:02}_{}",
+            solution.year, solution.day, solution.part
+        );
+        c.bench_function(&name, |b| {
+            b.iter(|| (solution.function)(std::hint::black_box(&input)))
+        });
+    }
 }
 
-macro_rules! benches {
-    ($($function:ident),* $(,)?) => {
-        $(bench!($function);)*
-        criterion_group!(benches, $($function,)*);
-        criterion_main!(benches);
-    };
-}
-
-benches!(
-    // 2022
-    benchmark_2022_01_1,
-    benchmark_2022_01_2,
-    benchmark_2022_02_1,
-    benchmark_2022_02_2,
-    benchmark_2022_03_1,
-    benchmark_2022_03_2,
-    benchmark_2022_04_1,
-    benchmark_2022_04_2,
-    benchmark_2022_05_1,
-    benchmark_2022_05_2,
-    benchmark_2022_06_1,
-    benchmark_2022_06_2,
-    benchmark_2022_07_1,
-    benchmark_2022_07_2,
-    benchmark_2022_08_1,
-    benchmark_2022_08_2,
-    benchmark_2022_09_1,
-    benchmark_2022_09_2,
-    benchmark_2022_10_1,
-    benchmark_2022_10_2,
-    benchmark_2022_11_1,
-    benchmark_2022_11_2,
-    benchmark_2022_12_1,
-    benchmark_2022_12_2,
-    benchmark_2022_13_1,
-    benchmark_2022_13_2,
-    // 2023
-    benchmark_2023_01_1,
-    benchmark_2023_01_2,
-    benchmark_2023_02_1,
-    benchmark_2023_02_2,
-    benchmark_2023_03_1,
-    benchmark_2023_03_2,
-    benchmark_2023_04_1,
-    benchmark_2023_04_2,
-    benchmark_2023_05_1,
-    benchmark_2023_05_2,
-    benchmark_2023_06_1,
-    benchmark_2023_06_2,
-    benchmark_2023_07_1,
-    benchmark_2023_07_2,
-    benchmark_2023_08_1,
-    benchmark_2023_08_2,
-    benchmark_2023_09_1,
-    benchmark_2023_09_2,
-    benchmark_2023_10_1,
-    benchmark_2023_10_2,
-    benchmark_2023_11_1,
-    benchmark_2023_11_2,
-    benchmark_2023_12_1,
-    benchmark_2023_12_2,
-    benchmark_2023_13_1,
-    benchmark_2023_13_2,
-    benchmark_2023_14_1,
-    benchmark_2023_14_2,
-    benchmark_2023_15_1,
-    benchmark_2023_15_2,
-    benchmark_2023_16_1,
-    benchmark_2023_16_2,
-    benchmark_2023_17_1,
-    benchmark_2023_17_2,
-    benchmark_2023_18_1,
-    benchmark_2023_18_2,
-    // 2024
-    benchmark_2024_01_1,
-    benchmark_2024_01_2,
-    benchmark_2024_02_1,
-    benchmark_2024_02_2,
-    benchmark_2024_03_1,
-    benchmark_2024_03_2,
-    benchmark_2024_04_1,
-    benchmark_2024_04_2,
-    benchmark_2024_05_1,
-    benchmark_2024_05_2,
-    benchmark_2024_06_1,
-    benchmark_2024_06_2,
-    benchmark_2024_07_1,
-    benchmark_2024_07_2,
-    benchmark_2024_08_1,
-    benchmark_2024_08_2,
-    benchmark_2024_09_1,
-    benchmark_2024_09_2,
-    benchmark_2024_10_1,
-    benchmark_2024_10_2,
-    benchmark_2024_11_1,
-    benchmark_2024_11_2,
-    benchmark_2024_12_1,
-    benchmark_2024_12_2,
-    benchmark_2024_13_1,
-    benchmark_2024_13_2,
-    benchmark_2024_14_1,
-    benchmark_2024_14_2,
-    benchmark_2024_15_1,
-    benchmark_2024_15_2,
-    benchmark_2024_16_1,
-    benchmark_2024_16_2,
-    benchmark_2024_17_1,
-    // 2025
-    benchmark_2025_01_1,
-    benchmark_2025_01_2,
-    benchmark_2025_02_1,
-    benchmark_2025_02_2,
-    benchmark_2025_03_1,
-    benchmark_2025_03_2,
-    benchmark_2025_04_1,
-    benchmark_2025_04_2,
-    benchmark_2025_05_1,
-    benchmark_2025_05_2,
-    benchmark_2025_06_1,
-    benchmark_2025_06_2,
-    benchmark_2025_07_1,
-    benchmark_2025_07_2,
-    benchmark_2025_08_1,
-    benchmark_2025_08_2,
-    benchmark_2025_09_1,
-    benchmark_2025_09_2,
-);
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
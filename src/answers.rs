@@ -0,0 +1,77 @@
+//! Known correct answers for regression checks
+//!
+//! The answers for a puzzle are stored in `{directory}/{year}/{day:02}.toml`
+//! with one string entry per part like `part1 = "1234"`.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Known correct answers for a certain puzzle
+pub struct Answers {
+    path: PathBuf,
+    parts: BTreeMap<usize, String>,
+}
+
+impl Answers {
+    /// Reads the answers for a puzzle from a directory (no file means no answers)
+    pub fn load(directory: &str, year: usize, day: usize) -> Result<Self, String> {
+        let path = PathBuf::from(format!("{directory}/{year}/{day:02}.toml"));
+        let mut parts = BTreeMap::new();
+        if path.exists() {
+            let table: toml::Table = std::fs::read_to_string(&path)
+                .or(Err(format!("couldn't read {}", path.display())))?
+                .parse()
+                .map_err(|error| format!("invalid answers in {}: {error}", path.display()))?;
+            for (key, value) in table {
+                let invalid = || format!("invalid answer {key} in {}", path.display());
+                let part = key.strip_prefix("part").and_then(|part| part.parse().ok());
+                let (Some(part), Some(answer)) = (part, value.as_str()) else {
+                    return Err(invalid());
+                };
+                parts.insert(part, answer.to_owned());
+            }
+        }
+        Ok(Self { path, parts })
+    }
+
+    /// Returns the known answer for a part of the puzzle
+    pub fn get(&self, part: usize) -> Option<&str> {
+        self.parts.get(&part).map(String::as_str)
+    }
+
+    /// Stores the answer for a part of the puzzle and writes the file
+    pub fn record(&mut self, part: usize, answer: &str) -> Result<(), String> {
+        self.parts.insert(part, answer.to_owned());
+        let table: toml::Table = (self.parts.iter())
+            .map(|(part, answer)| (format!("part{part}"), answer.as_str().into()))
+            .collect();
+        let error = || format!("couldn't write answers to {}", self.path.display());
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).or(Err(error()))?;
+        }
+        std::fs::write(&self.path, table.to_string()).or(Err(error()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn test_answers() {
+        let directory = std::env::temp_dir().join(format!("answers-{}", std::process::id()));
+        let directory = directory.to_str().unwrap();
+        let mut answers = Answers::load(directory, 2024, 3).unwrap();
+        assert_eq!(answers.get(1), None);
+        answers.record(2, "abc").unwrap();
+        answers.record(1, "123").unwrap();
+        answers.record(1, "456").unwrap();
+
+        let path = format!("{directory}/2024/03.toml");
+        let content = std::fs::read_to_string(path).unwrap();
+        let answers = Answers::load(directory, 2024, 3).unwrap();
+        std::fs::remove_dir_all(directory).unwrap();
+        assert_eq!(content, "part1 = \"456\"\npart2 = \"abc\"\n");
+        assert_eq!((answers.get(1), answers.get(2)), (Some("456"), Some("abc")));
+    }
+}
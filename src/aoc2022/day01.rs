@@ -2,46 +2,51 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/1)
 
-fn calories(elf: &str) -> i32 {
+use crate::{PuzzleError, error::parse};
+
+fn calories(input: &crate::PuzzleInput, elf: &str) -> Result<i32, PuzzleError> {
     let mut sum = 0;
     for line in elf.lines() {
-        let calories: i32 = line.parse().unwrap();
+        let calories: i32 = parse(input, line)?;
         sum += calories;
     }
-    sum
+    Ok(sum)
 }
 
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut max_calories = 0;
-    for elf in input.split("\n\n") {
-        max_calories = max_calories.max(calories(elf));
+    for elf in input.blocks() {
+        max_calories = max_calories.max(calories(input, elf)?);
     }
     Ok(max_calories.to_string())
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut top_calories = vec![0, 0, 0];
-    for elf in input.split("\n\n") {
-        top_calories.push(calories(elf));
+    for elf in input.blocks() {
+        top_calories.push(calories(input, elf)?);
         top_calories.sort();
         top_calories.remove(0);
     }
     Ok(top_calories.iter().sum::<i32>().to_string())
 }
 
+crate::register!(2022, 1, "Calorie Counting", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "24000");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "24000");
+        assert!(super::part1(&"1000\n2OOO".into()).is_err());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "45000");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "45000");
     }
 }
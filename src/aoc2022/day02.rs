@@ -4,6 +4,25 @@
 
 use std::collections::HashMap;
 
+use crate::PuzzleError;
+
+/// Returns the indices of both letters of a round looked up in their tables
+fn parse_round(
+    input: &crate::PuzzleInput,
+    round: &str,
+    tables: [&HashMap<&str, i32>; 2],
+) -> Result<[i32; 2], PuzzleError> {
+    let letters: Vec<&str> = round.split_whitespace().collect();
+    let [first, second] = letters[..] else {
+        return Err(PuzzleError::parse(input, round, "expected two letters"));
+    };
+    let lookup = |table: &HashMap<&str, i32>, letter| {
+        (table.get(letter).copied())
+            .ok_or_else(|| PuzzleError::parse(input, letter, "unknown letter"))
+    };
+    Ok([lookup(tables[0], first)?, lookup(tables[1], second)?])
+}
+
 fn outcome(difference: i32) -> i32 {
     match difference {
         0 => 3, // draw
@@ -13,14 +32,13 @@ fn outcome(difference: i32) -> i32 {
 }
 
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let action_index =
         HashMap::<_, i32>::from([("A", 0), ("B", 1), ("C", 2), ("X", 0), ("Y", 1), ("Z", 2)]);
     let mut score = 0;
     for round in input.lines() {
-        let mut actions = round.split_whitespace();
-        let action_index_1 = action_index.get(actions.next().unwrap()).unwrap();
-        let action_index_2 = action_index.get(actions.next().unwrap()).unwrap();
+        let [action_index_1, action_index_2] =
+            parse_round(input, round, [&action_index, &action_index])?;
         score += action_index_2 + 1; // shape score
         score += outcome((action_index_2 - action_index_1).rem_euclid(3));
     }
@@ -28,14 +46,13 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let action_index = HashMap::<_, i32>::from([("A", 0), ("B", 1), ("C", 2)]);
     let result_index = HashMap::<_, i32>::from([("X", 2), ("Y", 0), ("Z", 1)]);
     let mut score = 0;
     for round in input.lines() {
-        let mut actions = round.split_whitespace();
-        let action_index_1 = action_index.get(actions.next().unwrap()).unwrap();
-        let action_index_difference = *result_index.get(actions.next().unwrap()).unwrap();
+        let [action_index_1, action_index_difference] =
+            parse_round(input, round, [&action_index, &result_index])?;
         let action_index_2 = (action_index_1 + action_index_difference).rem_euclid(3);
         score += action_index_2 + 1; // shape score
         score += outcome(action_index_difference);
@@ -43,17 +60,21 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(score.to_string())
 }
 
+crate::register!(2022, 2, "Rock Paper Scissors", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "A Y\nB X\nC Z";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "15");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "15");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "12");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "12");
+        assert!(super::part2(&"A Y\nB W".into()).is_err());
+        assert!(super::part2(&"A Y\nB".into()).is_err());
     }
 }
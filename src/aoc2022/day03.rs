@@ -2,8 +2,24 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/3)
 
+use crate::PuzzleError;
+
+/// Returns the rucksacks unless they hold items other than letters
+fn rucksacks(input: &crate::PuzzleInput) -> Result<Vec<&str>, PuzzleError> {
+    let mut rucksacks = Vec::new();
+    for line in input.lines() {
+        let mut items = line.char_indices();
+        if let Some((index, item)) = items.find(|(_, item)| !item.is_ascii_alphabetic()) {
+            let item = &line[index..index + item.len_utf8()];
+            return Err(PuzzleError::parse(input, item, "item isn't a letter"));
+        }
+        rucksacks.push(line);
+    }
+    Ok(rucksacks)
+}
+
 fn priority(item: char) -> u32 {
-    let mut priority = item.to_digit(36).unwrap() - 9;
+    let mut priority = item.to_digit(36).unwrap_or_default() - 9;
     if item.is_ascii_uppercase() {
         priority += 26
     }
@@ -11,9 +27,9 @@ fn priority(item: char) -> u32 {
 }
 
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut sum = 0;
-    for line in input.lines() {
+    for line in rucksacks(input)? {
         let mid_index = line.len() / 2;
         let compartment1 = &line[..mid_index];
         let compartment2 = &line[mid_index..];
@@ -28,12 +44,15 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut sum = 0;
-    let mut rucksacks = input.lines();
+    let mut rucksacks = rucksacks(input)?.into_iter();
     while let Some(rucksack1) = rucksacks.next() {
-        let rucksack2 = rucksacks.next().unwrap();
-        let rucksack3 = rucksacks.next().unwrap();
+        let (Some(rucksack2), Some(rucksack3)) = (rucksacks.next(), rucksacks.next()) else {
+            return Err(PuzzleError::UnsupportedInput(
+                "number of rucksacks not a multiple of 3".to_owned(),
+            ));
+        };
         for item in rucksack1.chars() {
             if rucksack2.contains(item) & rucksack3.contains(item) {
                 sum += priority(item);
@@ -44,6 +63,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(sum.to_string())
 }
 
+crate::register!(2022, 3, "Rucksack Reorganization", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -57,11 +78,21 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "157");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "157");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "70");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "70");
+        let error = super::part1(&"abcA\nabé!".into()).unwrap_err();
+        assert!(matches!(
+            error,
+            crate::PuzzleError::Parse {
+                line: 2,
+                column: 3,
+                ..
+            }
+        ));
+        assert!(super::part2(&"ab\nab".into()).is_err());
     }
 }
@@ -2,24 +2,29 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/4)
 
-fn parse_line(line: &str) -> Result<(i32, i32, i32, i32), Box<dyn std::error::Error>> {
-    let (range1, range2) = line.split_once(",").ok_or("no ranges found")?;
-    let (lower1, upper1) = range1.split_once("-").ok_or("cannot parse range 1")?;
-    let (lower2, upper2) = range2.split_once("-").ok_or("cannot parse range 2")?;
-    Ok((
-        lower1.parse()?,
-        upper1.parse()?,
-        lower2.parse()?,
-        upper2.parse()?,
-    ))
+use crate::PuzzleError;
+use crate::interval::IntervalSet;
+
+type Assignment = IntervalSet<i32>;
+
+fn parse_line(
+    input: &crate::PuzzleInput,
+    line: &str,
+) -> Result<(Assignment, Assignment), PuzzleError> {
+    match input.numbers_in(line)?[..] {
+        [lower1, upper1, lower2, upper2] => {
+            Ok(((lower1..upper1 + 1).into(), (lower2..upper2 + 1).into()))
+        }
+        _ => Err(PuzzleError::parse(input, line, "expected two ranges")),
+    }
 }
 
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut count = 0;
     for line in input.lines() {
-        let (lower1, upper1, lower2, upper2) = parse_line(line)?;
-        if (lower1 - lower2) * (upper2 - upper1) >= 0 {
+        let (first, second) = parse_line(input, line)?;
+        if first.is_subset(&second) || second.is_subset(&first) {
             count += 1;
         }
     }
@@ -27,28 +32,30 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut count = 0;
     for line in input.lines() {
-        let (lower1, upper1, lower2, upper2) = parse_line(line)?;
-        if upper2 >= lower1 && lower2 <= upper1 {
+        let (first, second) = parse_line(input, line)?;
+        if !first.is_disjoint(&second) {
             count += 1;
         }
     }
     Ok(count.to_string())
 }
 
+crate::register!(2022, 4, "Camp Cleanup", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "2");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "2");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "4");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "4");
     }
 }
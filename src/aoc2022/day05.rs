@@ -2,19 +2,65 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/5)
 
+use crate::visualize::{Frame, Visualize};
+use crate::{Grid, PuzzleError, error::parse};
+
 enum Part {
     Part1,
     Part2,
 }
 use Part::*;
 
-fn solution(input: String, part: Part) -> crate::PuzzleResult {
-    let mut blocks = input.split("\n\n");
+/// Returns the stacks drawn like in the input
+fn draw(stacks: &[Vec<char>]) -> Grid<char> {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut picture = Grid::new((4 * stacks.len()).saturating_sub(1), height + 1, ' ');
+    for (k, stack) in stacks.iter().enumerate() {
+        for (level, &crt) in stack.iter().enumerate() {
+            let i = height - 1 - level;
+            picture[[i, 4 * k]] = '[';
+            picture[[i, 4 * k + 1]] = crt;
+            picture[[i, 4 * k + 2]] = ']';
+        }
+        picture[[height, 4 * k + 1]] = char::from_digit(k as u32 + 1, 36).unwrap_or('?');
+    }
+    picture
+}
+
+/// Returns the number of crates to move and the indices of the stacks to move
+/// them from and to
+fn parse_rearrangement(
+    input: &crate::PuzzleInput,
+    rearrangement: &str,
+    stack_count: usize,
+) -> Result<[usize; 3], PuzzleError> {
+    let words: Vec<&str> = rearrangement.split_whitespace().collect();
+    let ["move", number, "from", from, "to", dest] = words[..] else {
+        return Err(PuzzleError::parse(
+            input,
+            rearrangement,
+            "expected move N from A to B",
+        ));
+    };
+    let stack = |word| match parse(input, word)? {
+        number @ 1.. if number <= stack_count => Ok(number - 1),
+        _ => Err(PuzzleError::parse(input, word, "no such stack")),
+    };
+    Ok([parse(input, number)?, stack(from)?, stack(dest)?])
+}
+
+fn solution(
+    input: &crate::PuzzleInput,
+    part: Part,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
+    let mut blocks = input.blocks();
 
     // parse starting stacks
-    let starting_stacks = blocks.next().unwrap();
+    let starting_stacks = blocks.next().unwrap_or_default();
     let mut starting_rows = starting_stacks.lines().rev();
-    let numbers_row = starting_rows.next().unwrap();
+    let numbers_row = (starting_rows.next())
+        .ok_or_else(|| PuzzleError::MissingData("starting stacks".to_owned()))?;
     let mut stack_indices = Vec::new();
     let mut stacks = Vec::new();
     for (index, character) in numbers_row.chars().enumerate() {
@@ -24,55 +70,70 @@ fn solution(input: String, part: Part) -> crate::PuzzleResult {
         }
     }
     for row in starting_rows {
+        // Rows end early where stacks on the right are lower
         let row_chars: Vec<char> = row.chars().collect();
-        for i in 0..stack_indices.len() {
-            let character = row_chars[stack_indices[i]];
-            if character.is_alphabetic() {
-                stacks[i].push(character);
+        for (stack, &index) in stacks.iter_mut().zip(&stack_indices) {
+            if let Some(&character) = row_chars.get(index)
+                && character.is_alphabetic()
+            {
+                stack.push(character);
             }
         }
     }
 
     // parse and do rearrangements
-    let rearrangements = blocks.next().unwrap();
+    visualizer.frame(&|| Frame::new(draw(&stacks), "start"));
+    let rearrangements = (blocks.next())
+        .ok_or_else(|| PuzzleError::MissingData("blank line before rearrangements".to_owned()))?;
     for rearrangement in rearrangements.lines() {
-        let words: Vec<&str> = rearrangement.split_whitespace().collect();
-        let number: usize = words[1].parse().unwrap();
-        let from = words[3].parse::<usize>().unwrap() - 1;
-        let dest = words[5].parse::<usize>().unwrap() - 1;
-        match part {
-            Part1 => {
-                for _ in 0..number {
-                    let crt = stacks[from].pop().unwrap();
-                    stacks[dest].push(crt);
-                }
-            }
-            Part2 => {
-                let len = stacks[from].len();
-                let mut crates = stacks[from].split_off(len - number);
-                stacks[dest].append(&mut crates);
-            }
+        let [number, from, dest] = parse_rearrangement(input, rearrangement, stacks.len())?;
+        let len = stacks[from].len();
+        if number > len {
+            return Err(PuzzleError::parse(
+                input,
+                rearrangement,
+                format!("stack holds only {len} crates"),
+            ));
         }
+        let mut crates = stacks[from].split_off(len - number);
+        if let Part1 = part {
+            crates.reverse();
+        }
+        stacks[dest].append(&mut crates);
+        visualizer.frame(&|| Frame::new(draw(&stacks), rearrangement));
     }
 
     // costruct output
     let mut output = String::new();
-    for stack in stacks {
-        output.push(*stack.last().unwrap());
+    for (k, stack) in stacks.iter().enumerate() {
+        let top = (stack.last())
+            .ok_or_else(|| PuzzleError::NoSolution(format!("stack {} ends up empty", k + 1)))?;
+        output.push(*top);
     }
     Ok(output.to_string())
 }
 
+/// Solves a part emitting a frame for each rearrangement
+pub fn visualize(
+    input: &crate::PuzzleInput,
+    part: usize,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
+    solution(input, if part == 1 { Part1 } else { Part2 }, visualizer)
+}
+
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
-    solution(input, Part1)
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    solution(input, Part1, &mut ())
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
-    solution(input, Part2)
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    solution(input, Part2, &mut ())
 }
 
+crate::register!(2022, 5, "Supply Stacks", 1 => part1, 2 => part2, visualize => visualize);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -89,11 +150,43 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "CMZ");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "CMZ");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "MCD");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "MCD");
+        for (bad, replacement) in [
+            ("move 3 from 1 to 3", "move 4 from 1 to 3"),
+            ("move 3 from 1 to 3", "move 3 from 1 to 4"),
+            ("move 3 from 1 to 3", "move three from 1 to 3"),
+        ] {
+            let input = INPUT.replace(bad, replacement);
+            let error = super::part2(&input.into()).unwrap_err();
+            assert!(matches!(error, crate::PuzzleError::Parse { line: 7, .. }));
+        }
+    }
+
+    #[test]
+    fn test_visualize() {
+        let mut recording = crate::visualize::Recording::new();
+        super::visualize(&INPUT.into(), 1, &mut recording).unwrap();
+        let frames = recording.frames();
+        assert_eq!(frames.len(), 5);
+        assert_eq!(
+            frames[0].picture.to_string(),
+            INPUT.split("\n\n").next().unwrap()
+        );
+        assert_eq!(frames[4].caption, "move 1 from 1 to 2");
+        assert_eq!(
+            frames[4].picture.to_string(),
+            concat!(
+                "        [Z]\n",
+                "        [N]\n",
+                "        [D]\n",
+                "[C] [M] [P]\n",
+                " 1   2   3 ",
+            )
+        );
     }
 }
@@ -2,11 +2,16 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/6)
 
-fn solution(input: String, marker_size: usize) -> crate::PuzzleResult {
+use crate::PuzzleError;
+
+fn solution(input: &crate::PuzzleInput, marker_size: usize) -> crate::PuzzleResult {
     let mut characters = input.chars();
     let mut last = std::collections::VecDeque::new();
     for _ in 0..marker_size {
-        last.push_back(characters.next().unwrap());
+        let character = characters.next().ok_or_else(|| {
+            PuzzleError::UnsupportedInput(format!("fewer than {marker_size} characters"))
+        })?;
+        last.push_back(character);
     }
     let mut count = marker_size;
     loop {
@@ -27,22 +32,26 @@ fn solution(input: String, marker_size: usize) -> crate::PuzzleResult {
             last.pop_front();
             last.push_back(c);
         } else {
-            break;
+            return Err(PuzzleError::NoSolution(format!(
+                "no {marker_size} different characters in a row"
+            )));
         }
     }
     Ok(count.to_string())
 }
 
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, 4)
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, 14)
 }
 
+crate::register!(2022, 6, "Tuning Trouble", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUTS: [&str; 5] = [
@@ -57,15 +66,17 @@ mod tests {
     fn test_part1() {
         const RESULTS: [&str; INPUTS.len()] = ["7", "5", "6", "10", "11"];
         for i in 0..INPUTS.len() {
-            assert_eq!(&super::part1(INPUTS[i].to_string()).unwrap(), RESULTS[i]);
+            assert_eq!(&super::part1(&INPUTS[i].into()).unwrap(), RESULTS[i]);
         }
+        assert!(super::part1(&"abc".into()).is_err());
+        assert!(super::part1(&"abcabcabc".into()).is_err());
     }
 
     #[test]
     fn test_part2() {
         const RESULTS: [&str; INPUTS.len()] = ["19", "23", "23", "29", "26"];
         for i in 0..INPUTS.len() {
-            assert_eq!(&super::part2(INPUTS[i].to_string()).unwrap(), RESULTS[i]);
+            assert_eq!(&super::part2(&INPUTS[i].into()).unwrap(), RESULTS[i]);
         }
     }
 }
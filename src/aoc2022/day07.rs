@@ -2,6 +2,8 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/7)
 
+use crate::{PuzzleError, error::parse};
+
 struct Dir {
     parent: Option<usize>,
     children: std::collections::HashMap<String, usize>,
@@ -9,7 +11,7 @@ struct Dir {
     size: u32,
 }
 
-fn parse_commands(input: String) -> Vec<Dir> {
+fn parse_commands(input: &crate::PuzzleInput) -> Result<Vec<Dir>, PuzzleError> {
     // initialize file system with empty root directory
     let mut cwd = 0;
     let mut fs = vec![Dir {
@@ -22,51 +24,55 @@ fn parse_commands(input: String) -> Vec<Dir> {
     // loop over terminal output
     for line in input.lines() {
         let words: Vec<&str> = line.split_whitespace().collect();
-        if words[0] == "$" {
+        match words[..] {
             // change directory
-            if words[1] == "cd" {
-                cwd = match words[2] {
-                    "/" => 0,
-                    ".." => fs[cwd].parent.unwrap(),
-                    dir => *fs[cwd].children.get(dir).unwrap(),
-                }
+            ["$", "cd", "/"] => cwd = 0,
+            ["$", "cd", ".."] => {
+                cwd = (fs[cwd].parent)
+                    .ok_or_else(|| PuzzleError::parse(input, line, "root has no parent"))?
+            }
+            ["$", "cd", dir] => {
+                cwd = *(fs[cwd].children.get(dir))
+                    .ok_or_else(|| PuzzleError::parse(input, dir, "unknown directory"))?
             }
-        } else if words[0] == "dir" {
+            ["$", "ls"] => (),
             // add directory
-            let name = words[1];
-            if !fs[cwd].children.contains_key(name) {
-                let index = fs.len();
-                fs[cwd].children.insert(name.to_string(), index);
-                fs.push(Dir {
-                    parent: Some(cwd),
-                    children: std::collections::HashMap::new(),
-                    content: std::collections::HashMap::new(),
-                    size: 0,
-                });
+            ["dir", name] => {
+                if !fs[cwd].children.contains_key(name) {
+                    let index = fs.len();
+                    fs[cwd].children.insert(name.to_string(), index);
+                    fs.push(Dir {
+                        parent: Some(cwd),
+                        children: std::collections::HashMap::new(),
+                        content: std::collections::HashMap::new(),
+                        size: 0,
+                    });
+                }
             }
-        } else {
             // add file and increase size of parent directories
-            let name = words[1];
-            if !fs[cwd].content.contains_key(name) {
-                let size: u32 = words[0].parse().unwrap();
-                fs[cwd].content.insert(name.to_string(), size);
-                let mut index = cwd;
-                loop {
-                    fs[index].size += size;
-                    match fs[index].parent {
-                        Some(i) => index = i,
-                        None => break,
+            [size, name] => {
+                if !fs[cwd].content.contains_key(name) {
+                    let size: u32 = parse(input, size)?;
+                    fs[cwd].content.insert(name.to_string(), size);
+                    let mut index = cwd;
+                    loop {
+                        fs[index].size += size;
+                        match fs[index].parent {
+                            Some(i) => index = i,
+                            None => break,
+                        }
                     }
                 }
             }
+            _ => Err(PuzzleError::parse(input, line, "unknown terminal output"))?,
         }
     }
-    fs
+    Ok(fs)
 }
 
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let fs = parse_commands(input);
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let fs = parse_commands(input)?;
     let mut sum = 0;
     for dir in fs {
         if dir.size <= 100000 {
@@ -77,8 +83,8 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let fs = parse_commands(input);
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let fs = parse_commands(input)?;
     let mut size = fs[0].size;
     let needed = size - 40000000;
     for dir in fs {
@@ -89,6 +95,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(size.to_string())
 }
 
+crate::register!(2022, 7, "No Space Left On Device", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -119,11 +127,19 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "95437");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "95437");
+        for input in [
+            "$ cd /\n$ cd x",
+            "$ cd /\n$ ls\n12x a.txt",
+            "$ cd /\n$ ls\n1 2 3",
+        ] {
+            let error = super::part1(&input.into()).unwrap_err();
+            assert!(matches!(error, crate::PuzzleError::Parse { .. }));
+        }
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "24933642");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "24933642");
     }
 }
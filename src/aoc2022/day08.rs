@@ -2,40 +2,30 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/8)
 
-type Forrest = Vec<Vec<u32>>;
-
-fn read_forrest(input: String) -> Result<Forrest, String> {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|character| character.to_digit(10))
-                .collect()
-        })
-        .collect::<Option<Forrest>>()
-        .ok_or("couldn't read forrest".to_string())
-}
+use crate::Grid;
+
+type Forrest = Grid<u8>;
 
 fn update_reached_threshold(
-    max_height: &mut Option<u32>,
-    is_visible: &mut [Vec<bool>],
+    max_height: &mut Option<u8>,
+    is_visible: &mut Grid<bool>,
     forrest: &Forrest,
     (row_index, column_index): (usize, usize),
 ) -> bool {
-    let height = Some(forrest[row_index][column_index]);
+    let height = Some(forrest[[row_index, column_index]]);
     if height > *max_height {
         *max_height = height;
-        is_visible[row_index][column_index] = true;
+        is_visible[[row_index, column_index]] = true;
     }
     *max_height == Some(9)
 }
 
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let forrest = read_forrest(input)?;
-    let row_number = forrest.len();
-    let column_number = forrest[0].len();
-    let mut is_visible = vec![vec![false; column_number]; row_number];
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let forrest = input.digit_grid()?;
+    let row_number = forrest.height();
+    let column_number = forrest.width();
+    let mut is_visible = Grid::new(column_number, row_number, false);
 
     // look from left
     for i in 0..row_number {
@@ -78,32 +68,32 @@ pub fn part1(input: String) -> crate::PuzzleResult {
     }
 
     // count visible trees
-    let count = is_visible.into_iter().flatten().filter(|b| *b).count();
+    let count = is_visible.iter().filter(|b| **b).count();
     Ok(count.to_string())
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let forrest = read_forrest(input)?;
-    let row_number = forrest.len();
-    let column_number = forrest[0].len();
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let forrest = input.digit_grid()?;
+    let row_number = forrest.height();
+    let column_number = forrest.width();
     let mut max_score = 0;
     for i in 0..row_number {
         for j in 0..column_number {
-            let height = forrest[i][j];
+            let height = forrest[[i, j]];
 
             // look up
             let mut up_score = 0;
-            for row in forrest[..i].iter().rev() {
+            for h in forrest.column(j).take(i).rev() {
                 up_score += 1;
-                if row[j] >= height {
+                if *h >= height {
                     break;
                 }
             }
 
             // look left
             let mut left_score = 0;
-            for h in forrest[i][..j].iter().rev() {
+            for h in forrest.row(i)[..j].iter().rev() {
                 left_score += 1;
                 if *h >= height {
                     break;
@@ -112,7 +102,7 @@ pub fn part2(input: String) -> crate::PuzzleResult {
 
             // look right
             let mut right_score = 0;
-            for h in &forrest[i][(j + 1)..] {
+            for h in &forrest.row(i)[(j + 1)..] {
                 right_score += 1;
                 if *h >= height {
                     break;
@@ -121,9 +111,9 @@ pub fn part2(input: String) -> crate::PuzzleResult {
 
             // look down
             let mut down_score = 0;
-            for row in &forrest[(i + 1)..] {
+            for h in forrest.column(j).skip(i + 1) {
                 down_score += 1;
-                if row[j] >= height {
+                if *h >= height {
                     break;
                 }
             }
@@ -137,17 +127,19 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(max_score.to_string())
 }
 
+crate::register!(2022, 8, "Treetop Tree House", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "30373\n25512\n65332\n33549\n35390";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "21");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "21");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "8");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "8");
     }
 }
@@ -2,59 +2,123 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/9)
 
-fn solution<const ROPE_LENGTH: usize>(input: String) -> crate::PuzzleResult {
-    let mut rope_positions = [[0i32, 0i32]; ROPE_LENGTH];
-    let mut where_tail_was = std::collections::HashSet::from([rope_positions[ROPE_LENGTH - 1]]);
+use crate::geometry::{Dir4, Point};
+use crate::visualize::{Frame, Visualize};
+use crate::{Grid, PuzzleError, error::parse};
+use std::collections::HashSet;
+
+/// Returns the motions of the head split into single steps
+fn parse_steps(input: &crate::PuzzleInput) -> Result<Vec<Dir4>, PuzzleError> {
+    let mut steps = Vec::new();
     for line in input.lines() {
-        let motion: Vec<&str> = line.split_whitespace().collect();
-        let head_velocity = match motion[0] {
-            "R" => [1, 0],
-            "U" => [0, 1],
-            "L" => [-1, 0],
-            "D" => [0, -1],
-            _ => panic!(),
+        let error = || PuzzleError::parse(input, line, "expected direction and distance");
+        let (direction, distance) = line.split_once(' ').ok_or_else(error)?;
+        let direction = (direction.parse().ok())
+            .and_then(Dir4::from_letter)
+            .ok_or_else(error)?;
+        let distance: u8 = parse(input, distance)?;
+        steps.extend(std::iter::repeat_n(direction, distance as usize));
+    }
+    Ok(steps)
+}
+
+/// Returns the smallest and largest coordinates the head reaches which bound
+/// the knots as well since they only ever move towards the head
+fn bounds(steps: &[Dir4]) -> [Point<i32>; 2] {
+    let mut head = Point::new(0, 0);
+    let [mut min, mut max] = [head; 2];
+    for step in steps {
+        head += step.vector();
+        min = Point::new(min.x.min(head.x), min.y.min(head.y));
+        max = Point::new(max.x.max(head.x), max.y.max(head.y));
+    }
+    [min, max]
+}
+
+/// Returns the area within the bounds showing the knots (H for the head and
+/// then numbers) on top of the start (s) and where the tail was (#)
+fn draw<const ROPE_LENGTH: usize>(
+    [min, max]: [Point<i32>; 2],
+    rope: &[Point<i32>; ROPE_LENGTH],
+    where_tail_was: &HashSet<Point<i32>>,
+) -> Grid<char> {
+    let width = (max.x - min.x + 1) as usize;
+    let height = (max.y - min.y + 1) as usize;
+    let mut picture = Grid::new(width, height, '.');
+    let position = |point: Point<i32>| [(point.y - min.y) as usize, (point.x - min.x) as usize];
+    for &point in where_tail_was {
+        picture[position(point)] = '#';
+    }
+    picture[position(Point::new(0, 0))] = 's';
+    for (k, &knot) in rope.iter().enumerate().rev() {
+        picture[position(knot)] = match k {
+            0 => 'H',
+            _ if ROPE_LENGTH == 2 => 'T',
+            k => char::from_digit(k as u32, 10).unwrap_or('T'),
         };
-        let head_speed: u8 = motion[1].parse().unwrap();
-        for _ in 0..head_speed {
-            rope_positions[0][0] += head_velocity[0];
-            rope_positions[0][1] += head_velocity[1];
-            for k in 0..(ROPE_LENGTH - 1) {
-                for [i, j] in [[0, 1], [1, 0]] {
-                    if (rope_positions[k][i] - rope_positions[k + 1][i]).abs() == 2 {
-                        rope_positions[k + 1][i] =
-                            (rope_positions[k][i] + rope_positions[k + 1][i]) / 2;
-                        if (rope_positions[k][j] - rope_positions[k + 1][j]).abs() == 2 {
-                            rope_positions[k + 1][j] =
-                                (rope_positions[k][j] + rope_positions[k + 1][j]) / 2;
-                        } else {
-                            rope_positions[k + 1][j] = rope_positions[k][j];
-                        }
-                    }
-                }
+    }
+    picture
+}
+
+fn solution<const ROPE_LENGTH: usize>(
+    input: &crate::PuzzleInput,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
+    let steps = parse_steps(input)?;
+    let bounds = bounds(&steps);
+    let mut rope = [Point::new(0i32, 0); ROPE_LENGTH];
+    let mut where_tail_was = HashSet::from([rope[ROPE_LENGTH - 1]]);
+    visualizer.frame(&|| Frame::new(draw(bounds, &rope, &where_tail_was), "start"));
+    for (step, direction) in steps.iter().enumerate() {
+        rope[0] += direction.vector();
+        for k in 1..ROPE_LENGTH {
+            // knots follow diagonally if they aren't in the same row or column
+            let gap = rope[k - 1] - rope[k];
+            if gap.chebyshev() > 1 {
+                rope[k] += gap.signum();
             }
-            where_tail_was.insert(rope_positions[ROPE_LENGTH - 1]);
         }
+        where_tail_was.insert(rope[ROPE_LENGTH - 1]);
+        visualizer.frame(&|| {
+            let caption = format!("step {}: {}", step + 1, direction.to_arrow());
+            Frame::new(draw(bounds, &rope, &where_tail_was), caption)
+        });
     }
     Ok(where_tail_was.len().to_string())
 }
 
+/// Solves a part emitting a frame for each step of the head
+pub fn visualize(
+    input: &crate::PuzzleInput,
+    part: usize,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
+    if part == 1 {
+        solution::<2>(input, visualizer)
+    } else {
+        solution::<10>(input, visualizer)
+    }
+}
+
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
-    solution::<2>(input)
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    visualize(input, 1, &mut ())
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
-    solution::<10>(input)
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    visualize(input, 2, &mut ())
 }
 
+crate::register!(2022, 9, "Rope Bridge", 1 => part1, 2 => part2, visualize => visualize);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "13");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "13");
     }
 
     #[test]
@@ -62,7 +126,21 @@ mod tests {
         const INPUTS: [&str; 2] = [INPUT, "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20"];
         const RESULTS: [&str; INPUTS.len()] = ["1", "36"];
         for i in 0..INPUTS.len() {
-            assert_eq!(&super::part2(INPUTS[i].to_string()).unwrap(), RESULTS[i]);
+            assert_eq!(&super::part2(&INPUTS[i].into()).unwrap(), RESULTS[i]);
         }
     }
+
+    #[test]
+    fn test_visualize() {
+        let mut recording = crate::visualize::Recording::new();
+        super::visualize(&INPUT.into(), 1, &mut recording).unwrap();
+        let frames = recording.frames();
+        assert_eq!(frames.len(), 25);
+        let last = frames.last().unwrap();
+        assert_eq!(last.caption, "step 24: >");
+        assert_eq!(
+            last.picture.to_string(),
+            "..##..\n...##.\n.TH##.\n....#.\ns###.."
+        );
+    }
 }
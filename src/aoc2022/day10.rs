@@ -2,68 +2,92 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/10)
 
-enum Part {
-    Part1,
-    Part2,
+use crate::vm::{Effect, Instruction, Machine, Registers};
+use crate::{PuzzleError, error::parse};
+
+/// Index of the X register holding the sprite position
+const X: usize = 0;
+
+enum Cpu {
+    Noop,
+    Addx(i64),
 }
-use Part::*;
-
-fn solution(input: String, part: Part) -> crate::PuzzleResult {
-    let mut sprite_position: i32 = 1;
-    let mut cycle: u16 = 0;
-    let mut busy = false;
-    let mut add = None;
-    let mut sum = 0; // output for part 1
-    let mut string = String::new(); // output for part 2
-    let mut lines = input.lines();
-    loop {
-        if busy {
-            busy = false;
-        } else {
-            if let Some(value) = add {
-                sprite_position += value;
-                add = None;
-            }
-            if let Some(line) = lines.next() {
-                let mut cmd = line.split_whitespace();
-                if cmd.next().unwrap() == "addx" {
-                    let value: i32 = cmd.next().unwrap().parse().unwrap();
-                    add = Some(value);
-                    busy = true;
-                }
-            } else {
-                break;
-            }
+use Cpu::*;
+
+impl Instruction for Cpu {
+    type Registers = Registers<i64, 1>;
+    type Output = ();
+
+    fn cycles(&self) -> usize {
+        match self {
+            Noop => 1,
+            Addx(_) => 2,
         }
-        let ray_position = cycle.rem_euclid(40);
-        if ray_position == 0 {
-            string.push('\n');
+    }
+
+    fn execute(&self, registers: &mut Self::Registers) -> Effect<()> {
+        if let Addx(value) = self {
+            registers[X] += value;
         }
-        if ((sprite_position - 1)..=(sprite_position + 1)).contains(&(ray_position as i32)) {
-            string.push('#');
-        } else {
-            string.push('.');
+        Effect::Next
+    }
+}
+
+fn parse_program(input: &crate::PuzzleInput) -> Result<Machine<Cpu>, PuzzleError> {
+    let mut program = Vec::new();
+    for line in input.lines() {
+        program.push(match line.split_once(' ') {
+            None if line == "noop" => Noop,
+            Some(("addx", value)) => Addx(parse(input, value)?),
+            _ => Err(PuzzleError::parse(input, line, "unknown instruction"))?,
+        });
+    }
+    Ok(Machine::new(program, Registers([1])))
+}
+
+/// Part 1: Sum of signal strengths during every 40th cycle starting at 20
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let mut machine = parse_program(input)?;
+    let mut sum = 0;
+    machine.run_with_hook(|machine| {
+        let cycle = machine.cycle();
+        if cycle % 40 == 20 {
+            sum += cycle as i64 * machine.registers()[X];
         }
-        cycle += 1;
-        if (cycle + 20).rem_euclid(40) == 0 {
-            sum += cycle as i32 * sprite_position;
+    })?;
+    Ok(sum.to_string())
+}
+
+/// Returns the image drawn by the CRT
+fn draw_screen(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let mut machine = parse_program(input)?;
+    let mut image = String::new();
+    machine.run_with_hook(|machine| {
+        let column = (machine.cycle() - 1) % 40;
+        if column == 0 && !image.is_empty() {
+            image.push('\n');
         }
-    }
-    Ok(match part {
-        Part1 => sum.to_string(),
-        Part2 => string,
-    })
+        let sprite = machine.registers()[X];
+        let is_lit = (sprite - 1..=sprite + 1).contains(&(column as i64));
+        image.push(if is_lit { '#' } else { '.' });
+    })?;
+    Ok(image)
 }
 
-/// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
-    solution(input, Part1)
+/// Part 2: Letters drawn by the CRT
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let image = draw_screen(input)?;
+    crate::ocr::recognize(&image)
+        .map_err(|error| PuzzleError::NoSolution(format!("{error} in the image\n{image}")))
 }
-/// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
-    solution(input, Part2)
+
+/// Shows the image drawn by the CRT
+pub fn show(input: &crate::PuzzleInput, _part: usize) -> crate::PuzzleResult {
+    draw_screen(input)
 }
 
+crate::register!(2022, 10, "Cathode-Ray Tube", 1 => part1, 2 => part2, show => show);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -217,21 +241,29 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "13140");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "13140");
     }
 
     #[test]
-    fn test_part2() {
+    fn test_show() {
         assert_eq!(
-            super::part2(INPUT.to_string()).unwrap(),
+            super::show(&INPUT.into(), 2).unwrap(),
             concat!(
-                "\n##..##..##..##..##..##..##..##..##..##..",
-                "\n###...###...###...###...###...###...###.",
-                "\n####....####....####....####....####....",
-                "\n#####.....#####.....#####.....#####.....",
-                "\n######......######......######......####",
-                "\n#######.......#######.......#######....."
+                "##..##..##..##..##..##..##..##..##..##..\n",
+                "###...###...###...###...###...###...###.\n",
+                "####....####....####....####....####....\n",
+                "#####.....#####.....#####.....#####.....\n",
+                "######......######......######......####\n",
+                "#######.......#######.......#######....."
             )
         );
     }
+
+    #[test]
+    fn test_part2() {
+        // The example draws a test pattern instead of letters
+        let error = super::part2(&INPUT.into()).unwrap_err().to_string();
+        assert!(error.contains("unknown letter at column 0"));
+        assert!(error.ends_with("#######.......#######.......#######....."));
+    }
 }
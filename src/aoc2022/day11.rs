@@ -2,12 +2,21 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/11)
 
+use crate::{PuzzleError, PuzzleResult, error::parse};
+
+#[derive(Clone, Copy)]
+enum Operator {
+    Add,
+    Multiply,
+}
+
 struct Monkey {
     items: Vec<u64>,
-    operator: char,
-    operand: String,
+    operator: Operator,
+    /// Number the worry level is combined with or `None` for itself
+    operand: Option<u64>,
     divisor: u64,
-    receivers: Vec<usize>,
+    receivers: [usize; 2],
     inspected: u64,
 }
 
@@ -17,59 +26,84 @@ enum Part {
 }
 use Part::*;
 
-use crate::PuzzleResult;
-
-fn solution(input: String, part: Part) -> PuzzleResult {
-    let mut monkeys: Vec<Monkey> = input
-        .split("\n\n")
-        .map(|monkey_input| {
-            let mut lines = monkey_input.lines();
-            lines.next().unwrap();
-
-            let items: Vec<u64> = lines
-                .next()
-                .unwrap()
-                .split(": ")
-                .nth(1)
-                .unwrap()
-                .split(", ")
-                .map(|item| item.parse::<u64>().unwrap())
-                .collect();
-
-            let operation_data: Vec<&str> = lines
-                .next()
-                .unwrap()
-                .split(" = ")
-                .nth(1)
-                .unwrap()
-                .split_whitespace()
-                .skip(1)
-                .collect();
-
-            let divisor: u64 = lines
-                .next()
-                .unwrap()
-                .split_whitespace()
-                .nth(3)
-                .unwrap()
-                .parse()
-                .unwrap();
-
-            let receivers: Vec<usize> = lines
-                .take(2)
-                .map(|line| line.split_whitespace().nth(5).unwrap().parse().unwrap())
-                .collect();
-
-            Monkey {
-                items,
-                operator: operation_data[0].chars().next().unwrap(),
-                operand: operation_data[1].to_string(),
-                divisor,
-                receivers,
-                inspected: 0,
-            }
-        })
-        .collect();
+/// Returns the rest of a line after a prefix
+fn field<'a>(
+    input: &crate::PuzzleInput,
+    line: &'a str,
+    prefix: &str,
+) -> Result<&'a str, PuzzleError> {
+    (line.trim_start().strip_prefix(prefix))
+        .ok_or_else(|| PuzzleError::parse(input, line, format!("expected {prefix:?}")))
+}
+
+fn parse_monkey(input: &crate::PuzzleInput, block: &str) -> Result<Monkey, PuzzleError> {
+    let lines: Vec<&str> = block.lines().collect();
+    let [_, items, operation, test, if_true, if_false] = lines[..] else {
+        return Err(PuzzleError::parse(
+            input,
+            block,
+            "expected 6 lines describing a monkey",
+        ));
+    };
+
+    let items = (field(input, items, "Starting items:")?.split(','))
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| parse(input, item))
+        .collect::<Result<_, _>>()?;
+
+    let operation = field(input, operation, "Operation: new = old ")?;
+    let (operator, operand) = match operation.split_whitespace().collect::<Vec<_>>()[..] {
+        ["+", operand] => (Operator::Add, operand),
+        ["*", operand] => (Operator::Multiply, operand),
+        _ => Err(PuzzleError::parse(
+            input,
+            operation,
+            "expected + or * and an operand",
+        ))?,
+    };
+    let operand = match operand {
+        "old" => None,
+        operand => Some(parse(input, operand)?),
+    };
+
+    let divisor_text = field(input, test, "Test: divisible by ")?;
+    let divisor = match parse(input, divisor_text)? {
+        0 => Err(PuzzleError::parse(input, divisor_text, "division by zero"))?,
+        divisor => divisor,
+    };
+
+    let receivers = [
+        parse(input, field(input, if_true, "If true: throw to monkey ")?)?,
+        parse(input, field(input, if_false, "If false: throw to monkey ")?)?,
+    ];
+
+    Ok(Monkey {
+        items,
+        operator,
+        operand,
+        divisor,
+        receivers,
+        inspected: 0,
+    })
+}
+
+fn solution(input: &crate::PuzzleInput, part: Part) -> PuzzleResult {
+    let mut monkeys: Vec<Monkey> = (input.blocks())
+        .map(|block| parse_monkey(input, block))
+        .collect::<Result<_, _>>()?;
+    if monkeys.len() < 2 {
+        Err(PuzzleError::UnsupportedInput(
+            "fewer than two monkeys".to_owned(),
+        ))?
+    }
+    for (i, monkey) in monkeys.iter().enumerate() {
+        if let Some(&j) = monkey.receivers.iter().find(|&&j| j >= monkeys.len()) {
+            Err(PuzzleError::UnsupportedInput(format!(
+                "monkey {i} throws to missing monkey {j}"
+            )))?
+        }
+    }
 
     let (rounds, relief) = match part {
         Part1 => (20, 3),
@@ -82,15 +116,10 @@ fn solution(input: String, part: Part) -> PuzzleResult {
                 monkeys[i].inspected += 1;
 
                 // inspect
-                let operand = if monkeys[i].operand == "old" {
-                    item
-                } else {
-                    monkeys[i].operand.parse().unwrap()
-                };
+                let operand = monkeys[i].operand.unwrap_or(item);
                 item = match monkeys[i].operator {
-                    '+' => item + operand,
-                    '*' => item * operand,
-                    _ => panic!(),
+                    Operator::Add => item + operand,
+                    Operator::Multiply => item * operand,
                 };
                 item = match part {
                     Part1 => item / relief,
@@ -114,14 +143,16 @@ fn solution(input: String, part: Part) -> PuzzleResult {
 }
 
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, Part1)
 }
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, Part2)
 }
 
+crate::register!(2022, 11, "Monkey in the Middle", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -156,11 +187,23 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "10605");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "10605");
+        for (bad, replacement) in [
+            ("old * 19", "old / 19"),
+            ("by 19", "by 0"),
+            ("by 13", "by x"),
+            ("If true: throw to monkey 2", "If true: throw monkey 2"),
+        ] {
+            let input = INPUT.replace(bad, replacement);
+            let error = super::part1(&input.into()).unwrap_err();
+            assert!(matches!(error, crate::PuzzleError::Parse { .. }));
+        }
+        let input = INPUT.replace("monkey 3", "monkey 4");
+        assert!(super::part1(&input.into()).is_err());
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "2713310158");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "2713310158");
     }
 }
@@ -2,109 +2,108 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/12)
 
-struct Grid(Vec<Vec<char>>);
+use crate::Grid;
+use crate::search::{self, ShortestPaths};
 
-impl Grid {
-    fn width(&self) -> usize {
-        self.0[0].len()
-    }
-
-    fn height(&self) -> usize {
-        self.0.len()
-    }
-
-    fn get(&self, i: usize, j: usize) -> char {
-        self.0[i][j]
-    }
+struct HeightMap {
+    heights: Grid<char>,
+    end: [usize; 2],
+}
 
-    fn elevation(&self, i: usize, j: usize) -> u32 {
-        match self.get(i, j) {
+impl HeightMap {
+    fn elevation(&self, position: [usize; 2]) -> u32 {
+        match self.heights[position] {
             'S' => 0,
             'E' => 25,
             other => other.to_digit(36).unwrap() - 10,
         }
     }
 
-    fn dijkstra(&self, i_start: usize, j_start: usize) -> usize {
-        // initialize Dijkstra algorithm
-        let mut distance = vec![vec![usize::MAX; self.width()]; self.height()];
-        let mut to_visit = std::collections::HashSet::new();
-        distance[i_start][j_start] = 0;
-        to_visit.insert([i_start, j_start]);
-
-        'dijkstra: loop {
-            if to_visit.is_empty() {
-                break usize::MAX;
-            }
-
-            // visit location with shortest path to get there
-            let p_short = to_visit
-                .iter()
-                .min_by_key(|p| distance[p[0]][p[1]])
-                .cloned()
-                .unwrap();
-            to_visit.remove(&p_short);
-            let [i_short, j_short] = p_short;
-            let e_short = self.elevation(i_short, j_short);
-
-            // loop over reachable destinations
-            for (m, n) in [(0, -1), (-1, 0), (1, 0), (0, 1)] {
-                let i = (i_short as i32 + m) as usize; // -1 becomes usize::MAX and thus …
-                let j = (j_short as i32 + n) as usize; // … larger than width or height
-                if i < self.height() && j < self.width() && self.elevation(i, j) <= e_short + 1 {
-                    let d = distance[i_short][j_short] + 1;
-
-                    // final destination reached
-                    if self.get(i, j) == 'E' {
-                        break 'dijkstra d;
-                    }
-
-                    // update distance
-                    if distance[i][j] == usize::MAX {
-                        distance[i][j] = d;
-                        to_visit.insert([i, j]);
-                    } else if d < distance[i][j] {
-                        distance[i][j] = d;
-                    }
-                }
-            }
+    /// Returns the shortest paths from the best signal location down to the
+    /// nearest square matching a predicate, which are the reversed shortest
+    /// paths climbing up from any such square
+    fn descend(&self, mut is_start: impl FnMut(char) -> bool) -> ShortestPaths<[usize; 2], usize> {
+        let descendable = |&to: &[usize; 2]| {
+            let elevation = self.elevation(to);
+            (self.heights.neighbors4(to)).filter(move |&from| elevation <= self.elevation(from) + 1)
+        };
+        search::bfs([self.end], descendable, |&position| {
+            is_start(self.heights[position])
+        })
+    }
+
+    /// Returns the fewest steps from any square matching a predicate to the
+    /// best signal location
+    fn fewest_steps(&self, is_start: impl FnMut(char) -> bool) -> Option<usize> {
+        self.descend(is_start).goal_distance()
+    }
+
+    /// Returns the fewest steps from each square to the best signal location
+    /// unless it can't be reached
+    fn distance_field(&self) -> Grid<Option<usize>> {
+        let paths = self.descend(|_| false);
+        let mut field = Grid::new(self.heights.width(), self.heights.height(), None);
+        for (&position, &distance) in paths.distances() {
+            field[position] = Some(distance);
         }
+        field
     }
 }
 
-impl From<String> for Grid {
-    fn from(string: String) -> Self {
-        Self(string.lines().map(|line| line.chars().collect()).collect())
-    }
+fn parse_height_map(input: &crate::PuzzleInput) -> Result<HeightMap, crate::PuzzleError> {
+    let is_valid = |c: char| c.is_ascii_lowercase() || c == 'S' || c == 'E';
+    let heights = Grid::parse_with(input, |_, c| is_valid(c).then_some(c))?;
+    let end = (heights.find(&'E'))
+        .ok_or_else(|| crate::PuzzleError::MissingData("best signal location".to_owned()))?;
+    Ok(HeightMap { heights, end })
 }
 
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let grid = Grid::from(input);
-    for i in 0..grid.height() {
-        for j in 0..grid.width() {
-            if grid.get(i, j) == 'S' {
-                return Ok(grid.dijkstra(i, j).to_string());
-            }
-        }
-    }
-    Err("start not found")?
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let map = parse_height_map(input)?;
+    (map.fewest_steps(|c| c == 'S'))
+        .map(|steps| steps.to_string())
+        .ok_or_else(|| crate::PuzzleError::NoSolution("no path from S to E".to_owned()))
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let grid = Grid::from(input);
-    let mut shortest = usize::MAX;
-    for i in 0..grid.height() {
-        for j in 0..grid.width() {
-            if grid.get(i, j) == 'a' {
-                shortest = shortest.min(grid.dijkstra(i, j));
-            }
-        }
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let map = parse_height_map(input)?;
+    (map.fewest_steps(|c| c == 'a'))
+        .map(|steps| steps.to_string())
+        .ok_or_else(|| crate::PuzzleError::NoSolution("no path from a to E".to_owned()))
+}
+
+/// Fewest steps path drawn with arrows from the start of a part followed by
+/// a heatmap of the steps from every square for part 2
+pub fn show(input: &crate::PuzzleInput, part: usize) -> crate::PuzzleResult {
+    let map = parse_height_map(input)?;
+    let start = if part == 1 { 'S' } else { 'a' };
+    let paths = map.descend(|c| c == start);
+    let mut path = (paths.goals().first())
+        .and_then(|goal| paths.path(goal))
+        .ok_or_else(|| crate::PuzzleError::NoSolution(format!("no path from {start} to E")))?;
+    path.reverse();
+    let mut drawing = map.heights.map(|&c| if c == 'E' { c } else { '.' });
+    drawing.draw_path(&path);
+    if part == 1 {
+        return Ok(drawing.to_string());
     }
-    Ok(shortest.to_string())
+    Ok(format!("{drawing}\n\n{}", heatmap(&map.distance_field())))
 }
 
+/// Draws distances scaled to digits from 0 near the best signal location to
+/// 9 furthest away with unreachable squares left blank
+fn heatmap(field: &Grid<Option<usize>>) -> Grid<char> {
+    let furthest = field.iter().flatten().max().copied().unwrap_or_default();
+    field.map(|distance| match distance {
+        Some(distance) => char::from_digit((10 * distance / (furthest + 1)) as u32, 10).unwrap(),
+        None => ' ',
+    })
+}
+
+crate::register!(2022, 12, "Hill Climbing Algorithm", 1 => part1, 2 => part2, show => show);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -117,11 +116,34 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "31");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "31");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "29");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "29");
+    }
+
+    #[test]
+    fn test_show() {
+        for (part, steps) in [(1, 31), (2, 29)] {
+            let drawing = super::show(&INPUT.into(), part).unwrap();
+            assert_eq!(drawing.matches(['^', '>', 'v', '<']).count(), steps);
+            assert_eq!(drawing.lines().nth(2).unwrap().find('E'), Some(5));
+        }
+    }
+
+    #[test]
+    fn test_distance_field() {
+        let map = super::parse_height_map(&INPUT.into()).unwrap();
+        let field = map.distance_field();
+        assert_eq!(
+            (field[[0, 0]], field[[2, 5]], field[[4, 0]]),
+            (Some(31), Some(0), Some(29))
+        );
+        assert_eq!(map.fewest_steps(|c| c == 'c'), Some(26));
+        let map = super::parse_height_map(&"SzE".into()).unwrap();
+        assert_eq!(map.distance_field().row(0), [None, Some(1), Some(0)]);
+        assert_eq!(super::heatmap(&map.distance_field()).to_string(), " 50");
     }
 }
@@ -2,6 +2,8 @@
 //!
 //! [puzzle site](https://adventofcode.com/2022/day/13)
 
+use crate::{PuzzleError, error::parse};
+
 #[derive(PartialEq, Eq)]
 enum Packet {
     Integer(u32),
@@ -9,36 +11,39 @@ enum Packet {
 }
 
 impl Packet {
-    fn new(packet_string: &str) -> Self {
-        let mut chars = packet_string.chars();
-        if chars.next().unwrap() == '[' {
-            if chars.next().unwrap() == ']' {
-                Self::List(Vec::new())
-            } else {
-                let length = packet_string.len();
-                let list_body = &packet_string[1..length - 1];
-                let mut list = Vec::new();
-                let mut depth = 0;
-                let mut left = 0;
-                for (index, character) in list_body.char_indices() {
-                    match character {
-                        '[' => depth += 1,
-                        ']' => depth -= 1,
-                        ',' => {
-                            if depth == 0 {
-                                list.push(Self::new(&list_body[left..index]));
-                                left = index + 1;
-                            }
-                        }
-                        _ => (),
-                    }
+    fn new(input: &str, packet_string: &str) -> Result<Self, PuzzleError> {
+        let Some(list_body) = packet_string.strip_prefix('[') else {
+            return Ok(Self::Integer(parse(input, packet_string)?));
+        };
+        let list_body = (list_body.strip_suffix(']'))
+            .ok_or_else(|| PuzzleError::parse(input, packet_string, "list without closing ]"))?;
+        if list_body.is_empty() {
+            return Ok(Self::List(Vec::new()));
+        }
+        let mut list = Vec::new();
+        let mut depth = 0;
+        let mut left = 0;
+        for (index, character) in list_body.char_indices() {
+            match character {
+                '[' => depth += 1,
+                ']' if depth == 0 => {
+                    let bracket = &list_body[index..];
+                    return Err(PuzzleError::parse(input, bracket, "] without opening ["));
+                }
+                ']' => depth -= 1,
+                ',' if depth == 0 => {
+                    list.push(Self::new(input, &list_body[left..index])?);
+                    left = index + 1;
                 }
-                list.push(Self::new(&list_body[left..]));
-                Self::List(list)
+                _ => (),
             }
-        } else {
-            Self::Integer(packet_string.parse().unwrap())
         }
+        list.push(Self::new(input, &list_body[left..])?);
+        Ok(Self::List(list))
+    }
+
+    fn divider(number: u32) -> Self {
+        Self::List(vec![Self::List(vec![Self::Integer(number)])])
     }
 }
 
@@ -64,30 +69,37 @@ impl PartialOrd for Packet {
 }
 
 /// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
-    Ok(input
-        .split("\n\n")
-        .enumerate()
-        .map(|(index, pair)| {
-            let mut packets = pair.lines();
-            let packet1 = Packet::new(packets.next().unwrap());
-            let packet2 = Packet::new(packets.next().unwrap());
-            (packet1 < packet2) as usize * (index + 1)
-        })
-        .sum::<usize>()
-        .to_string())
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let mut sum = 0;
+    for (index, pair) in input.blocks().enumerate() {
+        let (packet1, packet2) = match *pair.lines().collect::<Vec<_>>() {
+            [packet1, packet2] => (Packet::new(input, packet1)?, Packet::new(input, packet2)?),
+            [] | [_] => Err(PuzzleError::MissingData(format!(
+                "second packet of pair {}",
+                index + 1
+            )))?,
+            _ => Err(PuzzleError::parse(
+                input,
+                pair,
+                "more than two packets in a pair",
+            ))?,
+        };
+        if packet1 < packet2 {
+            sum += index + 1;
+        }
+    }
+    Ok(sum.to_string())
 }
 
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let mut packets: Vec<Packet> = input
-        .lines()
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let mut packets = (input.lines())
         .filter(|line| !line.is_empty())
-        .map(Packet::new)
-        .collect();
+        .map(|line| Packet::new(input, line))
+        .collect::<Result<Vec<_>, _>>()?;
     packets.sort();
-    let divider1 = Packet::new("[[2]]");
-    let divider2 = Packet::new("[[6]]");
+    let divider1 = Packet::divider(2);
+    let divider2 = Packet::divider(6);
     let mut decoder_key = 0;
     for (index, packet) in packets.iter().enumerate() {
         if decoder_key == 0 && packet > &divider1 {
@@ -101,6 +113,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(decoder_key.to_string())
 }
 
+crate::register!(2022, 13, "Distress Signal", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -131,11 +145,31 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "13");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "13");
+        let error = super::part1(&"[1,2]\n[x]\n".into()).unwrap_err();
+        assert!(matches!(
+            error,
+            crate::PuzzleError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            }
+        ));
+        let error = super::part1(&"[1]\n[2]\n\n[[3],[4]\n[5]".into()).unwrap_err();
+        assert!(matches!(
+            error,
+            crate::PuzzleError::Parse {
+                line: 4,
+                column: 6,
+                ..
+            }
+        ));
+        let error = super::part1(&INPUT.replace("\n[[1],4]", "").into()).unwrap_err();
+        assert!(matches!(error, crate::PuzzleError::MissingData(_)));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "140");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "140");
     }
 }
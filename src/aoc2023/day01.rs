@@ -5,21 +5,23 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/1)
 
+use crate::{PuzzleError, error::parse};
+
 /// Part 1: Digits are single numerical characters
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut sum: usize = 0;
     for line in input.lines() {
         let mut hits = line.matches(|c: char| c.is_ascii_digit());
-        let first = hits.clone().next().ok_or(format!("no digit in {line}"))?;
-        let last = hits.next_back().ok_or(format!("no two digits in {line}"))?;
-        let number: usize = format!("{first}{last}").parse()?;
-        sum += number;
+        let no_digit = || PuzzleError::parse(input, line, "no digit");
+        let first = hits.clone().next().ok_or_else(no_digit)?;
+        let last = hits.next_back().ok_or_else(no_digit)?;
+        sum += 10 * parse::<usize>(input, first)? + parse::<usize>(input, last)?;
     }
     Ok(sum.to_string())
 }
 
 /// Part 2: Digits might be spelled out
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut digits = std::collections::HashMap::from([
         ("one".to_string(), 1),
         ("two".to_string(), 2),
@@ -40,26 +42,27 @@ pub fn part2(input: String) -> crate::PuzzleResult {
             .keys()
             .filter_map(|key| line.find(key).map(|index| (key, index)))
             .min_by(|a, b| a.1.cmp(&b.1))
-            .ok_or(format!("no digit in {line}"))?
+            .ok_or_else(|| PuzzleError::parse(input, line, "no digit"))?
             .0];
         let last = digits[digits
             .keys()
             .filter_map(|key| line.rfind(key).map(|index| (key, index)))
             .max_by(|a, b| a.1.cmp(&b.1))
-            .ok_or(format!("no two digits in {line}"))?
+            .ok_or_else(|| PuzzleError::parse(input, line, "no digit"))?
             .0];
-        let number: usize = format!("{first}{last}").parse()?;
-        sum += number;
+        sum += 10 * first + last;
     }
     Ok(sum.to_string())
 }
 
+crate::register!(2023, 1, "Trebuchet?!", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     #[test]
     fn test_part1() {
         let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
-        assert_eq!(&super::part1(input.to_string()).unwrap(), "142");
+        assert_eq!(&super::part1(&input.into()).unwrap(), "142");
     }
 
     #[test]
@@ -73,6 +76,6 @@ mod tests {
             "zoneight234\n",
             "7pqrstsixteen"
         );
-        assert_eq!(&super::part2(input.to_string()).unwrap(), "281");
+        assert_eq!(&super::part2(&input.into()).unwrap(), "281");
     }
 }
@@ -6,22 +6,24 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/2)
 
+use crate::{PuzzleError, error::parse};
+
 /// Part 1: Sum up IDs of possible games if the bag contained 12 red, 13 green,
 /// and 14 blue cubes
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut sum = 0;
     'lines: for line in input.lines() {
         if let Some((game, subsets)) = line.split_once(": ") {
             let id: usize = match game.split_once(" ") {
-                Some(("Game", number)) => number.parse()?,
-                _ => Err(format!("no game ID found in {line}"))?,
+                Some(("Game", number)) => parse(input, number)?,
+                _ => Err(PuzzleError::parse(input, line, "no game ID found"))?,
             };
             for cubes in subsets.split("; ").flat_map(|subset| subset.split(", ")) {
                 if match cubes.split_once(" ") {
-                    Some((number, "red")) => number.parse::<usize>()? > 12,
-                    Some((number, "green")) => number.parse::<usize>()? > 13,
-                    Some((number, "blue")) => number.parse::<usize>()? > 14,
-                    _ => Err(format!("invalid subset in {line}"))?,
+                    Some((number, "red")) => parse::<usize>(input, number)? > 12,
+                    Some((number, "green")) => parse::<usize>(input, number)? > 13,
+                    Some((number, "blue")) => parse::<usize>(input, number)? > 14,
+                    _ => Err(PuzzleError::parse(input, cubes, "invalid cubes"))?,
                 } {
                     continue 'lines;
                 }
@@ -34,17 +36,17 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 
 /// Part 2: Sum up over all games the products of the minimum number of cubes
 /// needed of each color
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut sum = 0;
     for line in input.lines() {
         let (mut reds, mut greens, mut blues) = (0, 0, 0);
         if let Some(subsets) = line.split(": ").last() {
             for cubes in subsets.split("; ").flat_map(|subset| subset.split(", ")) {
                 match cubes.split_once(" ") {
-                    Some((number, "red")) => reds = reds.max(number.parse()?),
-                    Some((number, "green")) => greens = greens.max(number.parse()?),
-                    Some((number, "blue")) => blues = blues.max(number.parse()?),
-                    _ => Err(format!("invalid subset in {line}"))?,
+                    Some((number, "red")) => reds = reds.max(parse(input, number)?),
+                    Some((number, "green")) => greens = greens.max(parse(input, number)?),
+                    Some((number, "blue")) => blues = blues.max(parse(input, number)?),
+                    _ => Err(PuzzleError::parse(input, cubes, "invalid cubes"))?,
                 }
             }
         }
@@ -53,6 +55,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(sum.to_string())
 }
 
+crate::register!(2023, 2, "Cube Conundrum", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -65,11 +69,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "8");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "8");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "2286");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "2286");
     }
 }
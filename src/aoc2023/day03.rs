@@ -7,8 +7,18 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/3)
 
+use crate::PuzzleError;
+
+fn parse_number(
+    input: &crate::PuzzleInput,
+    line: &str,
+    string: &str,
+) -> Result<usize, PuzzleError> {
+    (string.parse()).map_err(|error| PuzzleError::parse(input, line, format!("{error}: {string}")))
+}
+
 /// Part 1: Sum up all part numbers
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut sum = 0;
     let mut previous_symbol_positions = Vec::new();
     let mut previous_candidates = Vec::<(usize, usize, usize)>::new();
@@ -30,7 +40,7 @@ pub fn part1(input: String) -> crate::PuzzleResult {
             } else {
                 if let Some((start, string)) = &growing_number {
                     // End number
-                    candidates.push((*start, position, string.parse()?));
+                    candidates.push((*start, position, parse_number(input, line, string)?));
                     growing_number = None
                 }
                 if character != '.' {
@@ -41,7 +51,8 @@ pub fn part1(input: String) -> crate::PuzzleResult {
         }
         // End of line ends number
         if let Some((start, string)) = growing_number {
-            candidates.push((start, line.chars().count() - 1, string.parse()?));
+            let number = parse_number(input, line, &string)?;
+            candidates.push((start, line.chars().count() - 1, number));
         }
 
         // Check if previous candidates are part numbers
@@ -73,7 +84,7 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 }
 
 /// Part 2: Sum up all gear ratios
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut sum = 0;
     let mut previous_stars = Vec::<(usize, Vec<usize>)>::new();
     let mut previous_numbers = Vec::<(usize, usize, usize)>::new();
@@ -95,7 +106,7 @@ pub fn part2(input: String) -> crate::PuzzleResult {
             } else {
                 if let Some((start, string)) = &number {
                     // End number
-                    numbers.push((*start, position, string.parse()?));
+                    numbers.push((*start, position, parse_number(input, line, string)?));
                     number = None
                 }
                 if character == '*' {
@@ -106,7 +117,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
         }
         // End of line ends number
         if let Some((start, string)) = number {
-            numbers.push((start, line.chars().count() - 1, string.parse()?));
+            let number = parse_number(input, line, &string)?;
+            numbers.push((start, line.chars().count() - 1, number));
         }
 
         // Check new stars against previous and new numbers
@@ -139,6 +151,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(sum.to_string())
 }
 
+crate::register!(2023, 3, "Gear Ratios", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -156,11 +170,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "4361");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "4361");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "467835");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "467835");
     }
 }
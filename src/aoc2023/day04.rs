@@ -9,7 +9,7 @@
 ///
 /// On each line the first match is worth one point and every further match
 /// doubles the points for that line.
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut total = 0;
     for line in input.lines() {
         if let Some((winning, numbers)) = line
@@ -37,7 +37,7 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 ///
 /// Each card produces one copy of as many of the following cards as the card
 /// has matches.
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut total = 0;
     let mut following_copies = std::collections::VecDeque::new();
     for line in input.lines() {
@@ -64,6 +64,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(total.to_string())
 }
 
+crate::register!(2023, 4, "Scratchcards", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -77,11 +79,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "13");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "13");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "30");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "30");
     }
 }
@@ -11,142 +11,65 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/5)
 
-// Range of numbers mapping some distance in positive or negative direction
-struct MapRange {
-    range: std::ops::Range<usize>,
-    distance: usize,
-    is_subtracting: bool,
-}
+use crate::PuzzleError;
+use crate::interval::{IntervalMap, IntervalSet};
 
-// Return the next number from a string of whitespace-separated numbers
-fn next_number(numbers: &mut std::str::SplitWhitespace) -> Option<usize> {
-    numbers.next().and_then(|number| number.parse().ok())
+// Parse the seed numbers from the first line
+fn parse_seeds(input: &crate::PuzzleInput, line: Option<&str>) -> Result<Vec<i64>, PuzzleError> {
+    let numbers = (line.and_then(|line| line.strip_prefix("seeds: ")))
+        .ok_or_else(|| PuzzleError::MissingData("seeds".to_owned()))?;
+    input.numbers_in(numbers)
 }
 
-// Parse maps text blocks into a maps vector where each map is MapRange vector
-fn parse_maps(map_blocks: std::str::Split<&str>) -> Result<Vec<Vec<MapRange>>, String> {
-    let mut maps = Vec::new();
+// Parse map text blocks and compose them into one map from seeds to locations
+fn parse_almanac(
+    input: &crate::PuzzleInput,
+    map_blocks: std::str::Split<&str>,
+) -> Result<IntervalMap<i64>, PuzzleError> {
+    let mut almanac = IntervalMap::new();
     for block in map_blocks {
-        let (name, block) = block.split_once(":\n").ok_or("couldn't parse block")?;
-        let error = format!("couldn't parse {name}");
-        let mut ranges = Vec::new();
+        let (_, block) = (block.split_once(":\n"))
+            .ok_or_else(|| PuzzleError::parse(input, block, "map without header"))?;
+        let mut map = IntervalMap::new();
         for line in block.lines() {
-            let mut numbers = line.split_whitespace();
-            let first_destination = next_number(&mut numbers).ok_or(error.clone())?;
-            let first_source = next_number(&mut numbers).ok_or(error.clone())?;
-            let range_length = next_number(&mut numbers).ok_or(error.clone())?;
-            ranges.push(MapRange {
-                range: first_source..(first_source + range_length),
-                distance: first_destination.abs_diff(first_source),
-                is_subtracting: first_destination < first_source,
-            });
+            match input.numbers_in(line)?[..] {
+                [destination, source, length] => {
+                    map.insert(source..source + length, destination - source)
+                }
+                _ => Err(PuzzleError::parse(input, line, "expected three numbers"))?,
+            }
         }
-        maps.push(ranges);
+        almanac = almanac.then(&map);
     }
-    Ok(maps)
+    Ok(almanac)
 }
 
 /// Part 1: First line lists seeds
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let mut blocks = input.split("\n\n");
-
-    // Parse input
-    let seeds: Vec<usize> = blocks
-        .next()
-        .and_then(|line| line.strip_prefix("seeds: "))
-        .and_then(|numbers| {
-            numbers
-                .split_whitespace()
-                .map(|number| number.parse().ok())
-                .collect()
-        })
-        .ok_or("seeds not found")?;
-    let maps = parse_maps(blocks)?;
-
-    // Apply maps and find lowest location
-    let mut location = usize::MAX;
-    for seed in seeds {
-        let mut number = seed;
-        for map in &maps {
-            for map_range in map {
-                if map_range.range.contains(&number) {
-                    if map_range.is_subtracting {
-                        number -= map_range.distance;
-                    } else {
-                        number += map_range.distance;
-                    };
-                    break;
-                }
-            }
-        }
-        location = location.min(number);
-    }
-    Ok(location.to_string())
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let mut blocks = input.blocks();
+    let seeds = parse_seeds(input, blocks.next())?;
+    let almanac = parse_almanac(input, blocks)?;
+    (seeds.into_iter())
+        .map(|seed| almanac.get(seed))
+        .min()
+        .map(|location| location.to_string())
+        .ok_or_else(|| PuzzleError::NoSolution("no locations".to_owned()))
 }
 
 /// Part 2: First line lists seed ranges
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let mut blocks = input.split("\n\n");
-
-    // Parse input
-    let mut iter = blocks
-        .next()
-        .and_then(|line| line.strip_prefix("seeds: "))
-        .ok_or("seeds not found")?
-        .split_whitespace();
-    let mut ranges = Vec::<(usize, usize)>::new();
-    while let (Some(start), Some(length)) = (iter.next(), iter.next()) {
-        if let (Ok(start), Ok(length)) = (start.parse(), length.parse()) {
-            ranges.push((start, length));
-        }
-    }
-    let maps = parse_maps(blocks)?;
-
-    // Apply all maps to all seed ranges
-    for map in &maps {
-        let mut mapped_ranges = Vec::new();
-        for (start, length) in ranges {
-            let mut overlaps = Vec::new();
-            for map_range in map {
-                if map_range.range.start < start + length && start < map_range.range.end {
-                    let overlap_start = start.max(map_range.range.start);
-                    let overlap_length = (start + length).min(map_range.range.end) - overlap_start;
-                    overlaps.push((overlap_start, overlap_length));
-
-                    // Map overlap
-                    let mapped_start = if map_range.is_subtracting {
-                        overlap_start - map_range.distance
-                    } else {
-                        overlap_start + map_range.distance
-                    };
-                    mapped_ranges.push((mapped_start, overlap_length));
-                }
-            }
-
-            // Map unmatched regions
-            overlaps.sort();
-            let mut unmapped_start = start;
-            for (overlap_start, overlap_length) in overlaps {
-                if unmapped_start < overlap_start {
-                    mapped_ranges.push((unmapped_start, overlap_start - unmapped_start));
-                }
-                unmapped_start = overlap_start + overlap_length;
-            }
-            if unmapped_start < start + length {
-                mapped_ranges.push((unmapped_start, start + length - unmapped_start));
-            }
-        }
-        ranges = mapped_ranges;
-    }
-
-    // Find lowest location
-    if let Some(min) = ranges.iter().map(|(start, _)| start).min() {
-        Ok(min.to_string())
-    } else {
-        Err("no locations")?
-    }
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let mut blocks = input.blocks();
+    let seeds: IntervalSet<i64> = (parse_seeds(input, blocks.next())?.chunks_exact(2))
+        .map(|range| range[0]..range[0] + range[1])
+        .collect();
+    let almanac = parse_almanac(input, blocks)?;
+    (almanac.apply(&seeds).min())
+        .map(|location| location.to_string())
+        .ok_or_else(|| PuzzleError::NoSolution("no locations".to_owned()))
 }
 
+crate::register!(2023, 5, "If You Give A Seed A Fertilizer", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -187,11 +110,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "35");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "35");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "46");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "46");
     }
 }
@@ -18,6 +18,8 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/6)
 
+use crate::PuzzleError;
+
 fn ways((time, distance): (f64, f64)) -> usize {
     let sqrt = (time * time / 4.0 - distance).sqrt();
     let min = (time / 2.0 - sqrt).floor() as usize + 1;
@@ -27,21 +29,23 @@ fn ways((time, distance): (f64, f64)) -> usize {
 
 /// Part 1: Product of ways to reach further than the given distance in the
 /// given time over all input columns
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut lines = input.lines().map(|line| {
         line.split_whitespace()
             .skip(1)
             .filter_map(|number| number.parse::<f64>().ok())
     });
-    let times = lines.next().ok_or("times not found")?;
-    let distances = lines.next().ok_or("distances not found")?;
+    let times = lines
+        .next()
+        .ok_or(PuzzleError::MissingData("times".to_owned()))?;
+    let distances = (lines.next()).ok_or(PuzzleError::MissingData("distances".to_owned()))?;
     let product: usize = times.zip(distances).map(ways).product();
     Ok(product.to_string())
 }
 
 /// Part 2: Ways to reach further than the given distance in the given time
 /// where numbers are found by ignoring whitespace
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut lines = input.lines().filter_map(|line| {
         line.split_whitespace()
             .skip(1)
@@ -49,23 +53,29 @@ pub fn part2(input: String) -> crate::PuzzleResult {
             .parse::<f64>()
             .ok()
     });
-    let time = lines.next().ok_or("time not found")?;
-    let distance = lines.next().ok_or("distance not found")?;
+    let time = lines
+        .next()
+        .ok_or(PuzzleError::MissingData("time".to_owned()))?;
+    let distance = lines
+        .next()
+        .ok_or(PuzzleError::MissingData("distance".to_owned()))?;
     let ways = ways((time, distance));
     Ok(ways.to_string())
 }
 
+crate::register!(2023, 6, "Wait For It", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "Time:      7  15   30\nDistance:  9  40  200";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "288");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "288");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "71503");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "71503");
     }
 }
@@ -8,6 +8,8 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/7)
 
+use crate::{PuzzleError, error::parse};
+
 #[derive(Clone, Eq, Hash, Ord, PartialOrd, PartialEq)]
 enum Card {
     Joker,
@@ -110,8 +112,8 @@ enum Rules {
 use Rules::*;
 
 impl Rules {
-    fn try_card_from_char(&self, c: char) -> Result<Card, String> {
-        Ok(match c {
+    fn try_card_from_char(&self, c: char) -> Option<Card> {
+        Some(match c {
             '2' => Two,
             '3' => Three,
             '4' => Four,
@@ -128,29 +130,33 @@ impl Rules {
             'Q' => Queen,
             'K' => King,
             'A' => Ace,
-            _ => Err(format!("{c} is not a card"))?,
+            _ => None?,
         })
     }
 
-    fn try_hand_from_str(&self, s: &str) -> Result<Hand, Box<dyn std::error::Error>> {
-        let (cards, bid) = s.split_once(" ").ok_or("not a hand")?;
-        let mut cards = cards.chars().map(|c| self.try_card_from_char(c));
-        let error = "too few cards";
+    fn try_hand_from_str(&self, input: &crate::PuzzleInput, s: &str) -> Result<Hand, PuzzleError> {
+        let (cards, bid) =
+            (s.split_once(" ")).ok_or_else(|| PuzzleError::parse(input, s, "not a hand"))?;
+        let mut cards = cards.char_indices().map(|(index, c)| {
+            (self.try_card_from_char(c))
+                .ok_or_else(|| PuzzleError::parse(input, &s[index..], format!("{c} is not a card")))
+        });
+        let error = || PuzzleError::parse(input, bid, "too few cards");
         let cards = [
-            cards.next().ok_or(error)??,
-            cards.next().ok_or(error)??,
-            cards.next().ok_or(error)??,
-            cards.next().ok_or(error)??,
-            cards.next().ok_or(error)??,
+            cards.next().ok_or_else(error)??,
+            cards.next().ok_or_else(error)??,
+            cards.next().ok_or_else(error)??,
+            cards.next().ok_or_else(error)??,
+            cards.next().ok_or_else(error)??,
         ];
-        let bid = bid.parse()?;
+        let bid = parse(input, bid)?;
         Ok(Hand { cards, bid })
     }
 
-    fn solve_puzzle(&self, input: String) -> crate::PuzzleResult {
+    fn solve_puzzle(&self, input: &crate::PuzzleInput) -> crate::PuzzleResult {
         let mut hands = Vec::<Hand>::new();
         for line in input.lines() {
-            hands.push(self.try_hand_from_str(line)?);
+            hands.push(self.try_hand_from_str(input, line)?);
         }
         hands.sort();
         let mut total = 0;
@@ -162,26 +168,28 @@ impl Rules {
 }
 
 /// Part 1: Without jokers
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     WithoutJokers.solve_puzzle(input)
 }
 
 /// Part 2: With jokers
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     WithJokers.solve_puzzle(input)
 }
 
+crate::register!(2023, 7, "Camel Cards", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "6440");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "6440");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "5905");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "5905");
     }
 }
@@ -7,15 +7,26 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/8)
 
-use num::Integer; // lowest common multiple
+use itertools::Itertools;
 use regex::Regex;
 
-type Instructions<'a> = std::iter::Cycle<std::str::Chars<'a>>;
+use crate::{PuzzleError, math};
+
+type Instructions<'a> = &'a str;
 type Nodes<'a> = std::collections::HashMap<&'a str, (&'a str, &'a str)>;
 
-fn parse_input<'a>(input: &'a str) -> Result<(Instructions<'a>, Nodes<'a>), String> {
-    let blocks = input.split_once("\n\n").ok_or("no empty line found")?;
-    let instructions = blocks.0.chars().cycle();
+fn parse_input<'a>(input: &'a str) -> Result<(Instructions<'a>, Nodes<'a>), PuzzleError> {
+    let blocks = (input.split_once("\n\n")).ok_or(PuzzleError::MissingData(
+        "empty line after instructions".to_owned(),
+    ))?;
+    if let Some(index) = blocks.0.find(|c| c != 'L' && c != 'R') {
+        Err(PuzzleError::parse(
+            input,
+            &blocks.0[index..],
+            "invalid instruction",
+        ))?
+    }
+    let instructions = blocks.0;
     let re = Regex::new(r"([0-9A-Z]{3}) = \(([0-9A-Z]{3}), ([0-9A-Z]{3})\)").unwrap();
     let mut nodes = Nodes::new();
     for (_, [node, left, right]) in re.captures_iter(blocks.1).map(|c| c.extract()) {
@@ -28,18 +39,20 @@ fn follow_instruction<'a>(
     nodes: &'a Nodes,
     location: &str,
     instruction: char,
-) -> Result<&'a str, String> {
-    let targets = nodes.get(location).ok_or(format!("{location} not found"))?;
-    match instruction {
-        'L' => Ok(targets.0),
-        'R' => Ok(targets.1),
-        instruction => Err(format!("{instruction} is not a valid instruction")),
-    }
+) -> Result<&'a str, PuzzleError> {
+    let targets = (nodes.get(location))
+        .ok_or_else(|| PuzzleError::MissingData(format!("node {location}")))?;
+    Ok(if instruction == 'L' {
+        targets.0
+    } else {
+        targets.1
+    })
 }
 
 /// Part 1: Going from AAA to ZZZ
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let (mut instructions, nodes) = parse_input(&input)?;
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let (instructions, nodes) = parse_input(input)?;
+    let mut instructions = instructions.chars().cycle();
     let mut steps = 0;
     let mut location = "AAA";
     while location != "ZZZ" {
@@ -49,42 +62,85 @@ pub fn part1(input: String) -> crate::PuzzleResult {
     Ok(steps.to_string())
 }
 
-/// Part 2: Simultaneously from all ..A to only ..Z
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let (mut instructions, nodes) = parse_input(&input)?;
+/// Times at which a ghost walking from a start node is on a node ending with Z
+struct Arrivals {
+    /// Arrival times before the walk starts repeating
+    prefix: Vec<i64>,
+    /// Time at which the walk starts repeating
+    cycle_start: i64,
+    /// Number of steps after which the walk repeats
+    period: i64,
+    /// Arrival times within the first repetition
+    cyclic: Vec<i64>,
+}
 
-    // Collect start locations
-    let mut locations = std::collections::HashSet::new();
-    for &node in nodes.keys() {
-        if node.ends_with('A') {
-            locations.insert(node);
+impl Arrivals {
+    fn new(nodes: &Nodes, instructions: &str, start: &str) -> Result<Self, PuzzleError> {
+        let mut visited = std::collections::HashMap::new();
+        let mut arrivals = Vec::new();
+        let mut location = start;
+        for (time, (index, instruction)) in (0..).zip(instructions.chars().enumerate().cycle()) {
+            if let Some(&cycle_start) = visited.get(&(location, index)) {
+                let (prefix, cyclic) = arrivals.into_iter().partition(|&time| time < cycle_start);
+                return Ok(Self {
+                    prefix,
+                    cycle_start,
+                    period: time - cycle_start,
+                    cyclic,
+                });
+            }
+            visited.insert((location, index), time);
+            if location.ends_with('Z') {
+                arrivals.push(time);
+            }
+            location = follow_instruction(nodes, location, instruction)?;
         }
+        Err(PuzzleError::MissingData("instructions".to_owned()))
     }
 
-    // Iterate while not all paths reached their destination
-    let mut steps: usize = 0;
-    let mut lowest_common_multiple = 1;
-    while !locations.is_empty() {
-        steps += 1;
+    fn contains(&self, time: i64) -> bool {
+        self.prefix.contains(&time)
+            || time >= self.cycle_start
+                && (self.cyclic.iter()).any(|&arrival| (time - arrival) % self.period == 0)
+    }
+}
 
-        // Follow instructions
-        let instruction = instructions.next().unwrap();
-        for location in &locations.clone() {
-            locations.remove(location);
-            locations.insert(follow_instruction(&nodes, location, instruction)?);
-        }
+/// Part 2: Simultaneously from all ..A to only ..Z
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let (instructions, nodes) = parse_input(input)?;
+    let ghosts = (nodes.keys())
+        .filter(|node| node.ends_with('A'))
+        .map(|start| Arrivals::new(&nodes, instructions, start))
+        .collect::<Result<Vec<_>, _>>()?;
+    if ghosts.is_empty() {
+        Err(PuzzleError::MissingData("nodes ending with A".to_owned()))?
+    }
 
-        // Check if destination is reached
-        for location in &locations.clone() {
-            if location.ends_with('Z') {
-                locations.remove(location);
-                lowest_common_multiple = lowest_common_multiple.lcm(&steps);
-            }
+    // Arrivals before all walks repeat are checked one by one while later ones
+    // solve congruences for each combination of arrivals within the cycles
+    let mut times: Vec<i64> = ghosts
+        .iter()
+        .flat_map(|ghost| ghost.prefix.clone())
+        .collect();
+    let latest_cycle_start = ghosts.iter().map(|ghost| ghost.cycle_start).max().unwrap();
+    let cyclic_arrivals = (ghosts.iter())
+        .map(|ghost| (ghost.cyclic.iter()).map(|&arrival| (arrival, ghost.period)))
+        .multi_cartesian_product();
+    for congruences in cyclic_arrivals {
+        if let Some((residue, modulus)) = math::crt(congruences) {
+            let cycles = ((latest_cycle_start - residue).max(0) + modulus - 1) / modulus;
+            times.push(residue + cycles * modulus);
         }
     }
-    Ok(lowest_common_multiple.to_string())
+    (times.into_iter())
+        .filter(|&time| ghosts.iter().all(|ghost| ghost.contains(time)))
+        .min()
+        .map(|time| time.to_string())
+        .ok_or_else(|| PuzzleError::NoSolution("ghosts never arrive together".to_owned()))
 }
 
+crate::register!(2023, 8, "Haunted Wasteland", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -111,7 +167,7 @@ mod tests {
         ];
         const RESULTS: [&str; INPUTS.len()] = ["2", "6"];
         for i in 0..INPUTS.len() {
-            assert_eq!(&super::part1(INPUTS[i].to_string()).unwrap(), RESULTS[i]);
+            assert_eq!(&super::part1(&INPUTS[i].into()).unwrap(), RESULTS[i]);
         }
     }
 
@@ -129,6 +185,26 @@ mod tests {
             "22Z = (22B, 22B)\n",
             "XXX = (XXX, XXX)"
         );
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "6");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "6");
+    }
+
+    #[test]
+    fn test_part2_offset_cycles() {
+        // The first ghost arrives at 2, 5, 8, ... and the second one at 4, 8, 12, ...
+        const INPUT: &str = concat!(
+            "L\n",
+            "\n",
+            "11A = (11B, 11B)\n",
+            "11B = (11Z, 11Z)\n",
+            "11Z = (11C, 11C)\n",
+            "11C = (11D, 11D)\n",
+            "11D = (11Z, 11Z)\n",
+            "22A = (22B, 22B)\n",
+            "22B = (22C, 22C)\n",
+            "22C = (22D, 22D)\n",
+            "22D = (22Z, 22Z)\n",
+            "22Z = (22B, 22B)"
+        );
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "8");
     }
 }
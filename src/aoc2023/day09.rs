@@ -5,67 +5,70 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/9)
 
-enum Direction {
-    Left,
-    Right,
-}
-use Direction::*;
+use crate::{PuzzleError, error::parse};
 
-fn prediction(sequence: Vec<i64>, direction: &Direction) -> Option<i64> {
+/// Returns the next number of a sequence, or the previous one if `backwards`
+fn prediction(sequence: Vec<i64>, backwards: bool) -> Option<i64> {
     if sequence.iter().all(|d| *d == 0) {
         return Some(0);
     }
-    match direction {
-        Left => sequence.first(),
-        Right => sequence.last(),
-    }
-    .and_then(|last| {
+    let end = if backwards {
+        sequence.first()
+    } else {
+        sequence.last()
+    };
+    end.and_then(|&end| {
         prediction(
             sequence.windows(2).map(|w| w[1] - w[0]).collect(),
-            direction,
+            backwards,
         )
-        .map(|prediction| match direction {
-            Left => last - prediction,
-            Right => last + prediction,
+        .map(|prediction| {
+            if backwards {
+                end - prediction
+            } else {
+                end + prediction
+            }
         })
     })
 }
 
-fn solution(input: String, direction: Direction) -> crate::PuzzleResult {
+fn solution(input: &crate::PuzzleInput, backwards: bool) -> crate::PuzzleResult {
     let mut sum = 0;
     for line in input.lines() {
         sum += prediction(
             line.split_whitespace()
-                .map(|n| n.parse())
+                .map(|n| parse(input, n))
                 .collect::<Result<Vec<i64>, _>>()?,
-            &direction,
+            backwards,
         )
-        .ok_or(format!("prediction not possible for {line}"))?;
+        .ok_or_else(|| PuzzleError::parse(input, line, "no numbers to predict from"))?;
     }
     Ok(sum.to_string())
 }
 
 /// Part 1: Sum of first successors
-pub fn part1(input: String) -> crate::PuzzleResult {
-    solution(input, Right)
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    solution(input, false)
 }
 
 /// Part 2: Sum of first predecessors
-pub fn part2(input: String) -> crate::PuzzleResult {
-    solution(input, Left)
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    solution(input, true)
 }
 
+crate::register!(2023, 9, "Mirage Maintenance", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "114");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "114");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "2");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "2");
     }
 }
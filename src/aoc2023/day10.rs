@@ -4,29 +4,10 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/10)
 
+use crate::geometry::Dir4::{self, *};
+use crate::{Grid, PuzzleError};
 use num::Integer; // check whether number is odd
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Direction {
-    Down,
-    Left,
-    Right,
-    Up,
-}
-use Direction::*;
-const DIRECTIONS: [Direction; 4] = [Down, Left, Right, Up];
-
-impl Direction {
-    fn turn_around(&self) -> Self {
-        match self {
-            Down => Up,
-            Left => Right,
-            Right => Left,
-            Up => Down,
-        }
-    }
-}
-
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum Tile {
     BendNE,
@@ -41,8 +22,8 @@ use Tile::*;
 const TILES: [Tile; 7] = [BendNE, BendNW, BendSE, BendSW, Ground, Horizontal, Vertical];
 
 impl Tile {
-    fn try_from_char(character: char) -> Result<Self, String> {
-        Ok(match character {
+    fn try_from_char(character: char) -> Option<Self> {
+        Some(match character {
             'L' => BendNE,
             'J' => BendNW,
             'F' => BendSE,
@@ -50,11 +31,11 @@ impl Tile {
             '.' => Ground,
             '-' => Horizontal,
             '|' => Vertical,
-            _ => Err(format!("{character} is not a tile"))?,
+            _ => None?,
         })
     }
 
-    fn try_follow(&self, direction: Direction) -> Result<Direction, String> {
+    fn try_follow(&self, direction: Dir4) -> Result<Dir4, PuzzleError> {
         Ok(match (self, direction) {
             (BendNE, Down) => Right,
             (BendNE, Left) => Up,
@@ -68,67 +49,61 @@ impl Tile {
             (Horizontal, Right) => Right,
             (Vertical, Down) => Down,
             (Vertical, Up) => Up,
-            (tile, direction) => Err(format!("cannot enter {tile:?} moving {direction:?}"))?,
+            (tile, direction) => Err(PuzzleError::UnsupportedInput(format!(
+                "cannot enter {tile:?} moving {direction:?}"
+            )))?,
         })
     }
 }
 
 #[derive(Clone, Copy, PartialEq)]
 struct State {
-    location: (usize, usize),
-    direction: Direction,
+    location: [usize; 2],
+    direction: Dir4,
 }
 
-struct Grid {
-    tiles: Vec<Vec<Tile>>,
-    width: usize,
-    height: usize,
-}
+struct Sketch(Grid<Tile>);
 
-impl Grid {
+impl Sketch {
     fn try_step(
         &self,
         State {
-            location: (i, j),
+            location,
             direction,
         }: State,
-    ) -> Result<State, String> {
-        let (i, j) = match direction {
-            Down if i + 1 < self.height => (i + 1, j),
-            Left if j > 0 => (i, j - 1),
-            Right if j + 1 < self.width => (i, j + 1),
-            Up if i > 0 => (i - 1, j),
-            _ => Err(format!(
-                "{direction:?} from ({i}, {j}) would leave the grid"
-            ))?,
-        };
+    ) -> Result<State, PuzzleError> {
+        let location = (self.0.offset(location, direction.offset())).ok_or_else(|| {
+            PuzzleError::UnsupportedInput(format!(
+                "{direction:?} from {location:?} would leave the grid"
+            ))
+        })?;
         Ok(State {
-            location: (i, j),
-            direction: self.tiles[i][j].try_follow(direction)?,
+            location,
+            direction: self.0[location].try_follow(direction)?,
         })
     }
 
     fn try_turn_around(
         &self,
         State {
-            location: (i, j),
+            location,
             direction,
         }: State,
-    ) -> Result<State, String> {
+    ) -> Result<State, PuzzleError> {
         Ok(State {
-            location: (i, j),
-            direction: self.tiles[i][j].try_follow(direction.turn_around())?,
+            location,
+            direction: self.0[location].try_follow(direction.turn_around())?,
         })
     }
-    fn try_fitting_tile(&mut self, (i, j): (usize, usize)) -> Result<State, String> {
-        let original_tile = self.tiles[i][j];
+    fn try_fitting_tile(&mut self, location: [usize; 2]) -> Result<State, PuzzleError> {
+        let original_tile = self.0[location];
         // Try all tiles
         for tile in TILES {
-            self.tiles[i][j] = tile;
+            self.0[location] = tile;
             // Try all directions
-            for direction in DIRECTIONS {
+            for direction in Dir4::ALL {
                 let state = State {
-                    location: (i, j),
+                    location,
                     direction,
                 };
                 // Check if direction fits tile
@@ -141,36 +116,28 @@ impl Grid {
             }
         }
         // Reset tile if no fitting tile was found
-        self.tiles[i][j] = original_tile;
-        Err(format!("no tile fits at ({i}, {j})"))
+        self.0[location] = original_tile;
+        Err(PuzzleError::UnsupportedInput(format!(
+            "no tile fits at {location:?}"
+        )))
     }
 }
 
-fn parse_input(input: String) -> Result<(Grid, Vec<State>), String> {
+fn parse_input(input: &crate::PuzzleInput) -> Result<(Sketch, Vec<State>), PuzzleError> {
     // Fill grid and find start location
-    let mut tiles = Vec::new();
     let mut start_location = None;
-    for (i, line) in input.lines().enumerate() {
-        let mut row = Vec::new();
-        for (j, character) in line.chars().enumerate() {
-            row.push(if character == 'S' {
-                start_location = Some((i, j));
-                Ground // dummy tile (will be replace when the full grid is known)
-            } else {
-                Tile::try_from_char(character)?
-            })
+    let mut grid = Sketch(Grid::parse_with(input, |position, character| {
+        if character == 'S' {
+            start_location = Some(position);
+            Some(Ground) // dummy tile (will be replace when the full grid is known)
+        } else {
+            Tile::try_from_char(character)
         }
-        tiles.push(row);
-    }
-    let width = tiles[0].len();
-    let height = tiles.len();
-    let mut grid = Grid {
-        tiles,
-        width,
-        height,
-    };
+    })?);
     // Replace starting tile with fitting tile and get intial state
-    let original_state = grid.try_fitting_tile(start_location.ok_or("start not found")?)?;
+    let original_state = grid.try_fitting_tile(
+        start_location.ok_or_else(|| PuzzleError::MissingData("start".to_owned()))?,
+    )?;
 
     // Collect states along the pipe
     let mut pipe = vec![original_state];
@@ -184,39 +151,50 @@ fn parse_input(input: String) -> Result<(Grid, Vec<State>), String> {
 }
 
 /// Part 1: Half length of the pipe
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     Ok((parse_input(input)?.1.len() / 2).to_string())
 }
 
-/// Part 2: Points enclosed by the pipe
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let (grid, pipe) = parse_input(input)?;
-    let locations: Vec<(usize, usize)> = pipe.iter().map(|state| state.location).collect();
+fn enclosed_count(Sketch(grid): &Sketch, pipe: &[State]) -> usize {
+    let locations: Vec<[usize; 2]> = pipe.iter().map(|state| state.location).collect();
     let mut inside_count = 0;
-    for i in 0..grid.height {
-        for j in 0..grid.width {
-            // skip tiles on the pipe
-            if locations.contains(&(i, j)) {
-                continue;
-            }
-            // diagonal ray casting to avoid going parallel to pipe
-            let length = (grid.height - i).min(grid.width - j);
-            let mut crossings_count = 0;
-            for d in 1..length {
-                let (m, n) = (i + d, j + d);
-                // count crossing if pipe does not bend away from ray
-                if ![BendNE, BendSW].contains(&grid.tiles[m][n]) && locations.contains(&(m, n)) {
-                    crossings_count += 1;
-                }
-            }
-            if crossings_count.is_odd() {
-                inside_count += 1;
+    for [i, j] in grid.positions() {
+        // skip tiles on the pipe
+        if locations.contains(&[i, j]) {
+            continue;
+        }
+        // diagonal ray casting to avoid going parallel to pipe
+        let length = (grid.height() - i).min(grid.width() - j);
+        let mut crossings_count = 0;
+        for d in 1..length {
+            let location = [i + d, j + d];
+            // count crossing if pipe does not bend away from ray
+            if ![BendNE, BendSW].contains(&grid[location]) && locations.contains(&location) {
+                crossings_count += 1;
             }
         }
+        if crossings_count.is_odd() {
+            inside_count += 1;
+        }
     }
-    Ok(inside_count.to_string())
+    inside_count
 }
 
+/// Part 2: Points enclosed by the pipe
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let (grid, pipe) = parse_input(input)?;
+    Ok(enclosed_count(&grid, &pipe).to_string())
+}
+
+/// Both parts from one walk along the pipe
+pub fn both(input: &crate::PuzzleInput) -> (crate::PuzzleResult, crate::PuzzleResult) {
+    crate::both_results(
+        parse_input(input).map(|(grid, pipe)| [pipe.len() / 2, enclosed_count(&grid, &pipe)]),
+    )
+}
+
+crate::register!(2023, 10, "Pipe Maze", 1 => part1, 2 => part2, both => both);
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -227,7 +205,7 @@ mod tests {
         ];
         const RESULTS: [&str; INPUTS.len()] = ["4", "8"];
         for i in 0..INPUTS.len() {
-            assert_eq!(&super::part1(INPUTS[i].to_string()).unwrap(), RESULTS[i]);
+            assert_eq!(&super::part1(&INPUTS[i].into()).unwrap(), RESULTS[i]);
         }
     }
 
@@ -272,7 +250,7 @@ mod tests {
         ];
         const RESULTS: [&str; INPUTS.len()] = ["4", "8", "10"];
         for i in 0..INPUTS.len() {
-            assert_eq!(&super::part2(INPUTS[i].to_string()).unwrap(), RESULTS[i]);
+            assert_eq!(&super::part2(&INPUTS[i].into()).unwrap(), RESULTS[i]);
         }
     }
 }
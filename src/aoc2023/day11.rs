@@ -15,7 +15,7 @@ fn count_between(vector: &[bool], start: usize, end: usize) -> usize {
     vector[start..end].iter().filter(|&&b| b).count()
 }
 
-fn solution(input: String, age: usize) -> crate::PuzzleResult {
+fn solution(input: &crate::PuzzleInput, age: usize) -> crate::PuzzleResult {
     let mut rows = 0;
     let mut cols = 0;
     let mut galaxies = Vec::new();
@@ -47,15 +47,17 @@ fn solution(input: String, age: usize) -> crate::PuzzleResult {
 }
 
 /// Part 1: Empty rows and columns expand by two
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, 2)
 }
 
 /// Part 2: Empty rows and columns expand by a million
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, 1000000)
 }
 
+crate::register!(2023, 11, "Cosmic Expansion", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -72,7 +74,7 @@ mod tests {
     );
 
     fn test(age: usize, result: &str) {
-        assert_eq!(&super::solution(INPUT.to_string(), age).unwrap(), result);
+        assert_eq!(&super::solution(&INPUT.into(), age).unwrap(), result);
     }
 
     #[test]
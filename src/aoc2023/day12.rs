@@ -9,6 +9,8 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/12)
 
+use crate::{PuzzleError, error::parse};
+
 #[derive(Clone, PartialEq)]
 enum Status {
     Damaged,
@@ -18,12 +20,12 @@ enum Status {
 use Status::*;
 
 impl Status {
-    fn try_from_char(s: char) -> Result<Self, String> {
-        Ok(match s {
+    fn try_from_char(s: char) -> Option<Self> {
+        Some(match s {
             '#' => Damaged,
             '.' => Operational,
             '?' => Unknown,
-            _ => Err(format!("{s} is not a valid status"))?,
+            _ => None?,
         })
     }
 }
@@ -99,33 +101,40 @@ fn arrangements(statuses: &[Status], lengths: &[usize]) -> usize {
     arrangements_with_cache(statuses, lengths, &mut std::collections::HashMap::new())
 }
 
-fn solution(input: String, repeat: usize) -> crate::PuzzleResult {
+fn solution(input: &crate::PuzzleInput, repeat: usize) -> crate::PuzzleResult {
     let mut sum = 0;
     for line in input.lines() {
-        let (statuses, lengths) = line.split_once(" ").ok_or("missing space")?;
-        let statuses = format!("?{statuses}").repeat(repeat)[1..]
-            .chars()
-            .map(Status::try_from_char)
+        let (statuses, lengths) = line
+            .split_once(" ")
+            .ok_or_else(|| PuzzleError::parse(input, line, "missing space"))?;
+        let statuses = (statuses.char_indices())
+            .map(|(index, s)| {
+                Status::try_from_char(s)
+                    .ok_or_else(|| PuzzleError::parse(input, &statuses[index..], "invalid status"))
+            })
             .collect::<Result<Vec<_>, _>>()?;
-        let lengths = format!(",{lengths}").repeat(repeat)[1..]
-            .split(",")
-            .map(|number| number.parse())
+        let lengths = (lengths.split(","))
+            .map(|number| parse(input, number))
             .collect::<Result<Vec<usize>, _>>()?;
+        let statuses = vec![statuses; repeat].join(&Unknown);
+        let lengths = lengths.repeat(repeat);
         sum += arrangements(&statuses, &lengths);
     }
     Ok(sum.to_string())
 }
 
 /// Part 1: Don't fold
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, 1)
 }
 
 /// Part 2: Fold five times
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, 5)
 }
 
+crate::register!(2023, 12, "Hot Springs", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -139,11 +148,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "21");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "21");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "525152");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "525152");
     }
 }
@@ -6,9 +6,9 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/13)
 
-fn solution(input: String, with_smudges: bool) -> crate::PuzzleResult {
+fn solution(input: &crate::PuzzleInput, with_smudges: bool) -> crate::PuzzleResult {
     let mut sum = 0;
-    'pattern: for pattern in input.split("\n\n") {
+    'pattern: for pattern in input.blocks() {
         let pattern: Vec<Vec<char>> = pattern.lines().map(|line| line.chars().collect()).collect();
         let width = pattern[0].len();
         let height = pattern.len();
@@ -55,15 +55,17 @@ fn solution(input: String, with_smudges: bool) -> crate::PuzzleResult {
 }
 
 /// Part 1: Without smudges
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, false)
 }
 
 /// Part 2: With smudges
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, true)
 }
 
+crate::register!(2023, 13, "Point of Incidence", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -86,11 +88,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "405");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "405");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "400");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "400");
     }
 }
@@ -8,7 +8,10 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/14)
 
-use ndarray::Array2;
+use crate::Grid;
+use crate::cycle;
+use crate::geometry::Dir4::{self, *};
+use crate::grid::Tile;
 
 #[derive(Eq, Hash, Clone, Copy, PartialEq)]
 enum Field {
@@ -18,62 +21,72 @@ enum Field {
 }
 use Field::*;
 
-enum Direction {
-    North,
-    West,
-    South,
-    East,
+impl Tile for Field {
+    fn from_char(character: char) -> Option<Self> {
+        Some(match character {
+            'O' => Ball,
+            '#' => Cube,
+            '.' => Space,
+            _ => None?,
+        })
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Ball => 'O',
+            Cube => '#',
+            Space => '.',
+        }
+    }
 }
-use Direction::*;
 
 #[derive(Eq, Hash, Clone, PartialEq)]
-struct Platform(Array2<Field>);
+struct Platform(Grid<Field>);
 
 impl Platform {
     fn load(&self) -> usize {
         let mut sum = 0;
-        for column in self.0.columns() {
-            for (row, field) in column.iter().enumerate() {
-                if let Ball = field {
-                    sum += self.0.nrows() - row;
-                }
-            }
+        for (row, line) in self.0.rows().enumerate() {
+            let balls = line.iter().filter(|&&field| field == Ball).count();
+            sum += balls * (self.0.height() - row);
         }
         sum
     }
 
-    fn tilt(&mut self, direction: Direction) {
-        let end = match direction {
-            North | South => self.0.nrows(),
-            West | East => self.0.ncols(),
-        } as i32;
-        for mut line in match direction {
-            North | South => self.0.columns_mut(),
-            West | East => self.0.rows_mut(),
-        } {
+    fn tilt(&mut self, direction: Dir4) {
+        let (end, line_count) = match direction {
+            Up | Down => (self.0.height(), self.0.width()),
+            Left | Right => (self.0.width(), self.0.height()),
+        };
+        let end = end as i32;
+        for line in 0..line_count {
+            let at = |position: i32| match direction {
+                Up | Down => [position as usize, line],
+                Left | Right => [line, position as usize],
+            };
             let mut cube = match direction {
-                North | West => -1,
-                South | East => end,
+                Up | Left => -1,
+                Down | Right => end,
             };
             let mut count = 0;
             for mut position in 0..=end {
-                if let South | East = direction {
+                if let Down | Right = direction {
                     position = end - position - 1;
                 }
                 match if !(0..end).contains(&position) {
                     Cube
                 } else {
-                    line[position as usize]
+                    self.0[at(position)]
                 } {
                     Space => (),
                     Ball => count += 1,
                     Cube => {
                         // Sort balls and spaces between two cubes
                         for index in match direction {
-                            North | West => (cube + 1)..position,
-                            South | East => (position + 1)..cube,
+                            Up | Left => (cube + 1)..position,
+                            Down | Right => (position + 1)..cube,
                         } {
-                            line[index as usize] = if cube.abs_diff(index) <= count {
+                            self.0[at(index)] = if cube.abs_diff(index) <= count {
                                 Ball
                             } else {
                                 Space
@@ -88,62 +101,35 @@ impl Platform {
     }
 
     fn cycle(&mut self) {
-        self.tilt(North);
-        self.tilt(West);
-        self.tilt(South);
-        self.tilt(East);
-    }
-}
-
-impl std::str::FromStr for Platform {
-    type Err = Box<dyn std::error::Error>;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut fields = Vec::new();
-        let mut width = 0;
-        let mut height = 0;
-        for line in s.lines() {
-            height += 1;
-            width = 0;
-            for character in line.chars() {
-                width += 1;
-                fields.push(match character {
-                    'O' => Ball,
-                    '#' => Cube,
-                    '.' => Space,
-                    c => Err(format!("unexpected character {c}"))?,
-                })
-            }
-        }
-        Ok(Platform(Array2::from_shape_vec((height, width), fields)?))
+        self.tilt(Up);
+        self.tilt(Left);
+        self.tilt(Down);
+        self.tilt(Right);
     }
 }
 
 /// Part 1: Tilt north
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let mut platform: Platform = input.parse()?;
-    platform.tilt(North);
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let mut platform = Platform(input.parse()?);
+    platform.tilt(Up);
     Ok(platform.load().to_string())
 }
 
 /// Part 2: Tilt north, west, south, and east a billion times
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     const CYCLES: usize = 1000000000;
-    let mut platform: Platform = input.parse()?;
-    let mut hashes = std::collections::HashMap::new();
-    for cycle in 0..CYCLES {
+    let platform = Platform(input.parse()?);
+    let spin = |platform: &Platform| {
+        let mut platform = platform.clone();
         platform.cycle();
-        // Loop detection
-        if let Some(index) = hashes.get(&platform) {
-            for _ in 0..((CYCLES - cycle - 1) % (cycle - index)) {
-                platform.cycle();
-            }
-            break;
-        }
-        hashes.insert(platform.clone(), cycle);
-    }
-    Ok(platform.load().to_string())
+        platform
+    };
+    let repetition = cycle::brent(platform.clone(), spin);
+    Ok(repetition.nth(platform, spin, CYCLES).load().to_string())
 }
 
+crate::register!(2023, 14, "Parabolic Reflector Dish", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -161,11 +147,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "136");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "136");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "64");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "64");
     }
 }
@@ -8,6 +8,7 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/15)
 
+use crate::PuzzleError;
 use regex::Regex;
 
 fn hash(string: &str) -> usize {
@@ -19,9 +20,12 @@ fn hash(string: &str) -> usize {
 }
 
 /// Part 1: Sum of instruction hashes
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut sum = 0;
-    for step in input.lines().next().ok_or("empty input")?.split(",") {
+    for step in (input.lines().next())
+        .ok_or_else(|| PuzzleError::MissingData("steps".to_owned()))?
+        .split(",")
+    {
         sum += hash(step);
     }
     Ok(sum.to_string())
@@ -36,11 +40,15 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 /// end of the box. The focal power of a lense is the product of its focal
 /// length, the one-based index of its box, and its one-based position inside
 /// the box.
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let re = Regex::new("(?<label>[a-z]+)(-|=(?<focal_length>[1-9]))").unwrap();
     let mut boxes = vec![Vec::new(); 256];
-    for step in input.lines().next().ok_or("empty input")?.split(",") {
-        let step = re.captures(step).ok_or(format!("invalid step {step}"))?;
+    for step in (input.lines().next())
+        .ok_or_else(|| PuzzleError::MissingData("steps".to_owned()))?
+        .split(",")
+    {
+        let step =
+            (re.captures(step)).ok_or_else(|| PuzzleError::parse(input, step, "invalid step"))?;
         let label = step["label"].to_string();
         let index = hash(&label);
         match (
@@ -63,17 +71,19 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(sum.to_string())
 }
 
+crate::register!(2023, 15, "Lens Library", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "1320");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "1320");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "145");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "145");
     }
 }
@@ -8,34 +8,13 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/16)
 
-use itertools::Itertools;
-use ndarray::Array2;
+use std::collections::HashSet;
 
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct Location(i8, i8);
-
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-enum Direction {
-    Down,
-    Left,
-    Right,
-    Up,
-}
-use Direction::*;
-
-impl Direction {
-    fn step(&self, Location(i, j): Location) -> Location {
-        match *self {
-            Down => Location(i + 1, j),
-            Left => Location(i, j - 1),
-            Right => Location(i, j + 1),
-            Up => Location(i - 1, j),
-        }
-    }
-}
+use crate::Grid;
+use crate::geometry::Dir4::{self, *};
 
 #[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct BeamSegment(Location, Direction);
+struct BeamSegment([usize; 2], Dir4);
 
 #[derive(Clone, Copy)]
 enum MirrorOrientation {
@@ -45,7 +24,7 @@ enum MirrorOrientation {
 use MirrorOrientation::*;
 
 impl MirrorOrientation {
-    fn reflect(&self, direction: Direction) -> Direction {
+    fn reflect(&self, direction: Dir4) -> Dir4 {
         match (*self, direction) {
             (PositiveSlope, Down) | (NegativeSlope, Up) => Left,
             (PositiveSlope, Left) | (NegativeSlope, Right) => Down,
@@ -63,7 +42,7 @@ enum SplitterOrientation {
 use SplitterOrientation::*;
 
 impl SplitterOrientation {
-    fn pass(&self, direction: Direction) -> Vec<Direction> {
+    fn pass(&self, direction: Dir4) -> Vec<Dir4> {
         match (*self, direction) {
             (Horizontal, Down | Up) => vec![Left, Right],
             (Vertical, Left | Right) => vec![Down, Up],
@@ -80,16 +59,38 @@ enum Tile {
 }
 use Tile::*;
 
-struct Contraption(Array2<Tile>);
+impl crate::grid::Tile for Tile {
+    fn from_char(character: char) -> Option<Self> {
+        Some(match character {
+            '/' => Mirror(PositiveSlope),
+            '\\' => Mirror(NegativeSlope),
+            '.' => Space,
+            '-' => Splitter(Horizontal),
+            '|' => Splitter(Vertical),
+            _ => None?,
+        })
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Mirror(PositiveSlope) => '/',
+            Mirror(NegativeSlope) => '\\',
+            Space => '.',
+            Splitter(Horizontal) => '-',
+            Splitter(Vertical) => '|',
+        }
+    }
+}
+
+struct Contraption(Grid<Tile>);
 
 impl Contraption {
-    fn tile(&self, Location(i, j): Location) -> Option<&Tile> {
-        self.0.get((i as usize, j as usize))
+    fn tile(&self, location: [usize; 2]) -> Option<&Tile> {
+        self.0.get(location)
     }
 
-    fn step(&self, location: Location, direction: Direction) -> Option<Location> {
-        let location = direction.step(location);
-        self.tile(location).and(Some(location))
+    fn step(&self, location: [usize; 2], direction: Dir4) -> Option<[usize; 2]> {
+        self.0.offset(location, direction.offset())
     }
 
     fn propagate(&self, BeamSegment(location, direction): BeamSegment) -> Vec<BeamSegment> {
@@ -114,8 +115,8 @@ impl Contraption {
             .collect()
     }
 
-    fn energized(&self, beam_start: BeamSegment) -> usize {
-        let mut beam = std::collections::HashSet::new();
+    fn energized(&self, beam_start: BeamSegment) -> HashSet<[usize; 2]> {
+        let mut beam = HashSet::new();
         let mut segments = vec![beam_start];
         while !segments.is_empty() {
             let mut next_segments = Vec::new();
@@ -126,67 +127,66 @@ impl Contraption {
             }
             segments = next_segments;
         }
-        beam.into_iter().unique_by(|s| s.0).count()
+        beam.into_iter()
+            .map(|BeamSegment(location, _)| location)
+            .collect()
     }
-}
 
-impl std::str::FromStr for Contraption {
-    type Err = Box<dyn std::error::Error>;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut tiles = Vec::new();
-        let mut width = 0;
-        let mut height = 0;
-        for line in s.lines() {
-            height += 1;
-            width = 0;
-            for character in line.chars() {
-                width += 1;
-                tiles.push(match character {
-                    '/' => Mirror(PositiveSlope),
-                    '\\' => Mirror(NegativeSlope),
-                    '.' => Space,
-                    '-' => Splitter(Horizontal),
-                    '|' => Splitter(Vertical),
-                    c => Err(format!("unexpected character {c}"))?,
-                })
-            }
+    fn beam_starts(&self) -> impl Iterator<Item = BeamSegment> {
+        let width = self.0.width();
+        let height = self.0.height();
+        let rows = (0..height).flat_map(move |i| {
+            [
+                BeamSegment([i, 0], Right),
+                BeamSegment([i, width - 1], Left),
+            ]
+        });
+        let columns = (0..width)
+            .flat_map(move |j| [BeamSegment([0, j], Down), BeamSegment([height - 1, j], Up)]);
+        rows.chain(columns)
+    }
+
+    fn draw(&self, energized: &HashSet<[usize; 2]>) -> Grid<char> {
+        let mut drawing = self.0.map(crate::grid::Tile::to_char);
+        for &location in energized {
+            drawing[location] = '#';
         }
-        Ok(Contraption(Array2::from_shape_vec((height, width), tiles)?))
+        drawing
     }
 }
 
 /// Part 1: Tiles energized by a beam entering top left heading right
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let contraption: Contraption = input.parse()?;
-    let beam_start = BeamSegment(Location(0, 0), Right);
-    Ok(contraption.energized(beam_start).to_string())
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let contraption = Contraption(input.parse()?);
+    let beam_start = BeamSegment([0, 0], Right);
+    Ok(contraption.energized(beam_start).len().to_string())
 }
 
 /// Part 2: Maximum tiles energized by a single incident beam
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let contraption: Contraption = input.parse()?;
-    let width = contraption.0.ncols();
-    let height = contraption.0.nrows();
-    let mut max = 0;
-    for i in 0..height {
-        for beam_start in [
-            BeamSegment(Location(i as i8, 0), Right),
-            BeamSegment(Location(i as i8, width as i8 - 1), Left),
-        ] {
-            max = max.max(contraption.energized(beam_start));
-        }
-    }
-    for j in 0..width {
-        for beam_start in [
-            BeamSegment(Location(0, j as i8), Down),
-            BeamSegment(Location(height as i8 - 1, j as i8), Up),
-        ] {
-            max = max.max(contraption.energized(beam_start));
-        }
-    }
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let contraption = Contraption(input.parse()?);
+    let max = (contraption.beam_starts())
+        .map(|beam_start| contraption.energized(beam_start).len())
+        .max()
+        .unwrap_or(0);
     Ok(max.to_string())
 }
 
+/// Energized tiles marked `#`, for part 2 those of the best incident beam
+pub fn show(input: &crate::PuzzleInput, part: usize) -> crate::PuzzleResult {
+    let contraption = Contraption(input.parse()?);
+    let energized = match part {
+        1 => contraption.energized(BeamSegment([0, 0], Right)),
+        _ => (contraption.beam_starts())
+            .map(|beam_start| contraption.energized(beam_start))
+            .max_by_key(HashSet::len)
+            .unwrap_or_default(),
+    };
+    Ok(contraption.draw(&energized).to_string())
+}
+
+crate::register!(2023, 16, "The Floor Will Be Lava", 1 => part1, 2 => part2, show => show);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -204,11 +204,21 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "46");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "46");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "51");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "51");
+    }
+
+    #[test]
+    fn test_show() {
+        let drawing = super::show(&INPUT.into(), 1).unwrap();
+        assert!(drawing.starts_with("######....\n|#-.\\#....\n"));
+        assert_eq!(drawing.matches('#').count(), 46);
+        let drawing = super::show(&INPUT.into(), 2).unwrap();
+        assert!(drawing.starts_with(".#####....\n|#-#\\#....\n"));
+        assert_eq!(drawing.matches('#').count(), 51);
     }
 }
@@ -9,69 +9,38 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/17)
 
-use ndarray::{Array2, Dim};
+use crate::geometry::Dir4::{self, *};
+use crate::geometry::Point;
+use crate::grid::Tile;
+use crate::search::{self, ShortestPaths};
+use crate::{Grid, PuzzleError};
 
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct Location(i32, i32);
-
-const ORIGIN: Location = Location(0, 0);
-
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-enum Direction {
-    Down,
-    Left,
-    Right,
-    Up,
-}
-use Direction::*;
-
-impl Direction {
-    fn step(&self, Location(i, j): Location, d: usize) -> Location {
-        let d = d as i32;
-        match *self {
-            Down => Location(i + d, j),
-            Left => Location(i, j - d),
-            Right => Location(i, j + d),
-            Up => Location(i - d, j),
-        }
-    }
-
-    fn path(&self, location: Location, distance: usize) -> Vec<Location> {
-        let mut path = Vec::new();
-        for d in 1..=distance {
-            path.push(self.step(location, d));
-        }
-        path
-    }
+type Location = Point<i32>;
 
-    fn turn_left(&self) -> Direction {
-        match *self {
-            Down => Right,
-            Left => Down,
-            Right => Up,
-            Up => Left,
-        }
-    }
+const ORIGIN: Location = Point::new(0, 0);
 
-    fn turn_right(&self) -> Direction {
-        self.turn_left().turn_left().turn_left()
+fn path(location: Location, direction: Dir4, distance: usize) -> Vec<Location> {
+    let mut path = Vec::new();
+    for d in 1..=distance {
+        path.push(location + direction.vector() * d as i32);
     }
+    path
 }
 
 #[derive(Clone, Copy, Eq, Hash, PartialEq)]
-struct State<const MIN: usize, const MAX: usize>(Location, Direction, usize);
+struct State<const MIN: usize, const MAX: usize>(Location, Dir4, usize);
 
 impl<const MIN: usize, const MAX: usize> State<MIN, MAX> {
     fn neighbors(&self) -> Vec<(Vec<Location>, Self)> {
         let &Self(location, direction, count) = self;
         let mut states = Vec::new();
         if count + MIN < MAX {
-            let path = direction.path(location, 1);
+            let path = path(location, direction, 1);
             let location = *path.last().unwrap();
             states.push((path, Self(location, direction, count + 1)));
         }
         for direction in [direction.turn_left(), direction.turn_right()] {
-            let path = direction.path(location, MIN);
+            let path = path(location, direction, MIN);
             let location = *path.last().unwrap();
             states.push((path, Self(location, direction, 0)));
         }
@@ -79,119 +48,88 @@ impl<const MIN: usize, const MAX: usize> State<MIN, MAX> {
     }
 }
 
-struct HeatMap(Array2<u32>);
+struct HeatMap(Grid<u8>);
 
 impl HeatMap {
-    fn heat(&self, Location(i, j): Location) -> Option<u32> {
-        self.0.get((i as usize, j as usize)).copied()
+    fn heat(&self, location: Location) -> Option<u32> {
+        self.0.get(location.position()?).map(|&heat| heat.into())
     }
 
     fn collect_heat(&self, path: Vec<Location>) -> Option<u32> {
         path.iter().map(|&location| self.heat(location)).sum()
     }
-}
-
-impl std::str::FromStr for HeatMap {
-    type Err = Box<dyn std::error::Error>;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut heats = Vec::new();
-        let mut width = 0;
-        let mut height = 0;
-        for line in s.lines() {
-            height += 1;
-            width = 0;
-            for c in line.chars() {
-                width += 1;
-                heats.push(c.to_digit(10).ok_or(format!("{c} is not a digit"))?)
-            }
-        }
-        Ok(HeatMap(Array2::from_shape_vec((height, width), heats)?))
-    }
-}
 
-struct Losses<const MIN: usize, const MAX: usize>(Array2<Vec<u32>>);
-
-impl<const MIN: usize, const MAX: usize> Losses<MIN, MAX> {
-    fn new(dim: Dim<[usize; 2]>) -> Self {
-        Losses(Array2::from_elem(dim, vec![u32::MAX; 4 * (MAX - MIN) + 2]))
+    /// Returns the paths of least heat loss from the origin to the
+    /// bottom-right corner
+    fn least_heat_loss<const MIN: usize, const MAX: usize>(
+        &self,
+    ) -> ShortestPaths<State<MIN, MAX>, u32> {
+        let destination = Point::new(self.0.width() as i32 - 1, self.0.height() as i32 - 1);
+        search::dijkstra(
+            [State(ORIGIN, Left, MAX), State(ORIGIN, Up, MAX)],
+            |state| {
+                (state.neighbors().into_iter())
+                    .filter_map(|(path, neighbor)| Some((neighbor, self.collect_heat(path)?)))
+            },
+            |state| state.0 == destination,
+        )
     }
 
-    fn index(direction: Direction, count: usize) -> usize {
-        if count + MIN < MAX {
-            count
-                + (MAX - MIN)
-                    * match direction {
-                        Down => 0,
-                        Left => 1,
-                        Right => 2,
-                        Up => 3,
-                    }
-        } else {
-            4 * (MAX - MIN)
-                + match direction {
-                    Down | Up => 0,
-                    Left | Right => 1,
+    /// Draws the path of a crucible with arrows in the direction it enters
+    /// each block
+    fn draw<const MIN: usize, const MAX: usize>(&self, states: &[State<MIN, MAX>]) -> Grid<char> {
+        let mut drawing = self.0.map(|heat| heat.to_char());
+        for step in states.windows(2) {
+            let (State(from, _, _), State(to, direction, _)) = (step[0], step[1]);
+            let distance = from.manhattan_distance(to) as usize;
+            for location in path(from, direction, distance) {
+                if let Some(position) = location.position() {
+                    drawing[position] = direction.to_arrow();
                 }
+            }
         }
+        drawing
     }
+}
 
-    fn get(&self, State(Location(i, j), direction, count): State<MIN, MAX>) -> Option<u32> {
-        self.0
-            .get((i as usize, j as usize))
-            .map(|array| array[Self::index(direction, count)])
-    }
-
-    fn update(&mut self, State(Location(i, j), direction, count): State<MIN, MAX>, loss: u32) {
-        if let Some(array) = self.0.get_mut((i as usize, j as usize)) {
-            let index = Self::index(direction, count);
-            array[index] = array[index].min(loss);
-        }
-    }
+fn solution<const MIN: usize, const MAX: usize>(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let map = HeatMap(input.digit_grid()?);
+    (map.least_heat_loss::<MIN, MAX>().goal_distance())
+        .map(|loss| loss.to_string())
+        .ok_or_else(|| PuzzleError::NoSolution("destination unreachable".to_owned()))
 }
 
-fn solution<const MIN: usize, const MAX: usize>(input: String) -> crate::PuzzleResult {
-    let map: HeatMap = input.parse()?;
-    let mut losses = Losses::<MIN, MAX>::new(map.0.raw_dim());
-    let mut to_visit = std::collections::HashSet::new();
-    for initial_state in [State(ORIGIN, Left, MAX), State(ORIGIN, Up, MAX)] {
-        losses.update(initial_state, 0);
-        to_visit.insert(initial_state);
-    }
-    let destination = Location(map.0.nrows() as i32 - 1, map.0.ncols() as i32 - 1);
-    let optimal_loss = 'dijkstra: loop {
-        let best = to_visit
-            .iter()
-            .min_by_key(|&&state| losses.get(state).unwrap())
-            .copied()
-            .unwrap();
-        to_visit.remove(&best);
-        let best_loss = losses.get(best).unwrap();
-        for (path, neighbor) in best.neighbors() {
-            if let Some(extra_loss) = map.collect_heat(path) {
-                let neighbor_loss = best_loss + extra_loss;
-                if neighbor.0 == destination {
-                    break 'dijkstra neighbor_loss;
-                }
-                if losses.get(neighbor) == Some(u32::MAX) {
-                    to_visit.insert(neighbor);
-                }
-                losses.update(neighbor, neighbor_loss);
-            }
-        }
-    };
-    Ok(optimal_loss.to_string())
+fn show_solution<const MIN: usize, const MAX: usize>(
+    input: &crate::PuzzleInput,
+) -> crate::PuzzleResult {
+    let map = HeatMap(input.digit_grid()?);
+    let paths = map.least_heat_loss::<MIN, MAX>();
+    let states = (paths.goals().first())
+        .and_then(|goal| paths.path(goal))
+        .ok_or_else(|| PuzzleError::NoSolution("destination unreachable".to_owned()))?;
+    Ok(map.draw(&states).to_string())
 }
 
 /// Part 1: Forward steps mustn't be more than 3 before turn
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution::<1, 3>(input)
 }
 
 /// Part 2: Forward steps must be between 4 and 10 before turn
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution::<4, 10>(input)
 }
 
+/// Path of least heat loss drawn with arrows in the direction of the crucible
+pub fn show(input: &crate::PuzzleInput, part: usize) -> crate::PuzzleResult {
+    match part {
+        1 => show_solution::<1, 3>(input),
+        _ => show_solution::<4, 10>(input),
+    }
+}
+
+crate::register!(2023, 17, "Clumsy Crucible", 1 => part1, 2 => part2, show => show);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -212,13 +150,26 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "102");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "102");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "94");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "94");
         let input = "111111111111\n999999999991\n999999999991\n999999999991\n999999999991";
-        assert_eq!(&super::part2(input.to_string()).unwrap(), "71");
+        assert_eq!(&super::part2(&input.into()).unwrap(), "71");
+    }
+
+    #[test]
+    fn test_show() {
+        for (part, loss) in [(1, 102), (2, 94)] {
+            let drawing = super::show(&INPUT.into(), part).unwrap();
+            assert!(drawing.starts_with('2') && drawing.ends_with(['>', 'v']));
+            let heat: u32 = (drawing.chars().zip(INPUT.chars()))
+                .filter(|(c, _)| ['^', '>', 'v', '<'].contains(c))
+                .map(|(_, heat)| heat.to_digit(10).unwrap())
+                .sum();
+            assert_eq!(heat, loss);
+        }
     }
 }
@@ -7,51 +7,129 @@
 //!
 //! [puzzle site](https://adventofcode.com/2023/day/18)
 
+use crate::geometry::{Dir4, Point};
+use crate::{Grid, PuzzleError, error::parse};
 use regex::Regex;
 
-enum Direction {
-    Down,
-    Left,
-    Right,
-    Up,
-}
-use Direction::*;
+/// Longest side of a lagoon in meters which is still drawn
+const MAX_DRAWING_SIZE: i64 = 2000;
 
 // Shoelace formula plus outer half of the trench
-fn lagoon_volume(dig_plan: impl Iterator<Item = (Direction, i64)>) -> i64 {
+fn lagoon_volume(dig_plan: impl Iterator<Item = (Dir4, i64)>) -> i64 {
     let mut lagoon_volume = 0;
     let mut trench_length = 0;
-    let mut location = (0, 0);
+    let mut location = Point::new(0, 0);
     for (direction, distance) in dig_plan {
-        let (x_1, y_1) = location;
-        location = match direction {
-            Down => (x_1, y_1 - distance),
-            Left => (x_1 - distance, y_1),
-            Right => (x_1 + distance, y_1),
-            Up => (x_1, y_1 + distance),
-        };
-        let (x_2, y_2) = location;
+        let Point { x: x_1, y: y_1 } = location;
+        location += direction.vector() * distance;
+        let Point { x: x_2, y: y_2 } = location;
         lagoon_volume += x_1 * y_2 - x_2 * y_1;
         trench_length += distance;
     }
     (lagoon_volume.abs() + trench_length) / 2 + 1
 }
 
-/// Part 1: Direction in first column, distance in second
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let dig_plan = input.lines().flat_map(|line| {
-        let mut split = line.split(' ');
-        let direction: Option<Direction> = split.next().and_then(|s| match s {
-            "D" => Some(Down),
-            "L" => Some(Left),
-            "R" => Some(Right),
-            "U" => Some(Up),
-            _ => None,
-        });
-        let distance: Option<i64> = split.next().and_then(|s| s.parse().ok());
-        direction.zip(distance)
+// Trench marked `#` and interior `~` found by crossing vertical trench
+// segments in the middle of each row
+fn draw_lagoon(dig_plan: impl Iterator<Item = (Dir4, i64)>) -> Result<Grid<char>, PuzzleError> {
+    let mut corners = vec![Point::new(0, 0)];
+    for (direction, distance) in dig_plan {
+        let location = corners[corners.len() - 1] + direction.vector() * distance;
+        corners.push(location);
+    }
+    let (x_min, x_max, y_min, y_max) = (corners.iter()).fold((0, 0, 0, 0), |bounds, p| {
+        let (x_min, x_max, y_min, y_max) = bounds;
+        (
+            x_min.min(p.x),
+            x_max.max(p.x),
+            y_min.min(p.y),
+            y_max.max(p.y),
+        )
     });
-    Ok(lagoon_volume(dig_plan).to_string())
+    let (width, height) = (x_max - x_min + 1, y_max - y_min + 1);
+    if width.max(height) > MAX_DRAWING_SIZE {
+        return Err(PuzzleError::UnsupportedInput(format!(
+            "lagoon of {width} by {height} meters too large to draw"
+        )));
+    }
+    let cell = |x: i64, y: i64| [(y - y_min) as usize, (x - x_min) as usize];
+    let mut drawing = Grid::new(width as usize, height as usize, '.');
+    for pair in corners.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        for x in from.x.min(to.x)..=from.x.max(to.x) {
+            for y in from.y.min(to.y)..=from.y.max(to.y) {
+                drawing[cell(x, y)] = '#';
+            }
+        }
+    }
+    for y in y_min..=y_max {
+        let mut crossings: Vec<i64> = (corners.windows(2))
+            .filter(|pair| pair[0].x == pair[1].x)
+            .filter(|pair| (pair[0].y.min(pair[1].y)..pair[0].y.max(pair[1].y)).contains(&y))
+            .map(|pair| pair[0].x)
+            .collect();
+        crossings.sort_unstable();
+        for inside in crossings.chunks_exact(2) {
+            for x in inside[0]..=inside[1] {
+                if drawing[cell(x, y)] == '.' {
+                    drawing[cell(x, y)] = '~';
+                }
+            }
+        }
+    }
+    Ok(drawing)
+}
+
+type DigPlan = Vec<(Dir4, i64)>;
+
+fn dig_plan(input: &crate::PuzzleInput) -> Result<DigPlan, PuzzleError> {
+    (input.lines())
+        .map(|line| {
+            let mut split = line.split_whitespace();
+            let (Some(direction), Some(distance)) = (split.next(), split.next()) else {
+                return Err(PuzzleError::parse(
+                    input,
+                    line,
+                    "expected direction and distance",
+                ));
+            };
+            let mut letters = direction.chars();
+            let direction = match (letters.next().and_then(Dir4::from_letter), letters.next()) {
+                (Some(direction), None) => direction,
+                _ => Err(PuzzleError::parse(
+                    input,
+                    direction,
+                    "expected U, R, D or L",
+                ))?,
+            };
+            Ok((direction, parse(input, distance)?))
+        })
+        .collect()
+}
+
+fn hex_dig_plan(input: &crate::PuzzleInput) -> Result<DigPlan, PuzzleError> {
+    let re = Regex::new(r"\(#([0-9a-f]{5})([0-3])\)").unwrap();
+    (input.lines())
+        .map(|line| {
+            let (_, [distance, direction]) = (re.captures(line))
+                .ok_or_else(|| PuzzleError::parse(input, line, "expected color like (#70c710)"))?
+                .extract();
+            let direction = match direction {
+                "0" => Dir4::Right,
+                "1" => Dir4::Down,
+                "2" => Dir4::Left,
+                _ => Dir4::Up,
+            };
+            let distance = i64::from_str_radix(distance, 16)
+                .map_err(|error| PuzzleError::parse(input, distance, error))?;
+            Ok((direction, distance))
+        })
+        .collect()
+}
+
+/// Part 1: Direction in first column, distance in second
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    Ok(lagoon_volume(dig_plan(input)?.into_iter()).to_string())
 }
 
 /// Part 2: Third column holds direction and distance
@@ -59,27 +137,23 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 /// The third column is in parentheses and holds a hex triplet with a leading
 /// number sign. The last digit represents the direction and the other digits
 /// represent the distance.
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let re = Regex::new(r"\(#([0-9a-f]{6})\)").unwrap();
-    let dig_plan = input.lines().flat_map(|line| {
-        re.captures(line).and_then(|cap| {
-            cap.get(1).and_then(|m| {
-                let mut chars = m.as_str().chars();
-                let direction = match chars.next_back() {
-                    Some('0') => Some(Right),
-                    Some('1') => Some(Down),
-                    Some('2') => Some(Left),
-                    Some('3') => Some(Up),
-                    _ => None,
-                };
-                let distance = i64::from_str_radix(chars.as_str(), 16).ok();
-                direction.zip(distance)
-            })
-        })
-    });
-    Ok(lagoon_volume(dig_plan).to_string())
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    Ok(lagoon_volume(hex_dig_plan(input)?.into_iter()).to_string())
 }
 
+/// Lagoon with the trench marked `#` and the dug out interior `~`
+///
+/// The lagoon of part 2 is millions of meters wide and isn't drawn.
+pub fn show(input: &crate::PuzzleInput, part: usize) -> crate::PuzzleResult {
+    let drawing = match part {
+        1 => draw_lagoon(dig_plan(input)?.into_iter())?,
+        _ => draw_lagoon(hex_dig_plan(input)?.into_iter())?,
+    };
+    Ok(drawing.to_string())
+}
+
+crate::register!(2023, 18, "Lavaduct Lagoon", 1 => part1, 2 => part2, show => show);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -101,11 +175,40 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "62");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "62");
+        for (bad, replacement) in [("R 2 (#7807d2)", "X 2 (#7807d2)"), ("R 2 (#7807d2)", "R")] {
+            let input = INPUT.replace(bad, replacement);
+            let error = super::part1(&input.into()).unwrap_err();
+            assert!(matches!(error, crate::PuzzleError::Parse { line: 11, .. }));
+        }
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "952408144115");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "952408144115");
+        for (bad, replacement) in [("(#7807d2)", "(#7807d4)"), ("(#7807d2)", "#7807d2")] {
+            let input = INPUT.replace(bad, replacement);
+            let error = super::part2(&input.into()).unwrap_err();
+            assert!(matches!(error, crate::PuzzleError::Parse { line: 11, .. }));
+        }
+    }
+
+    #[test]
+    fn test_show() {
+        let drawing = super::show(&INPUT.into(), 1).unwrap();
+        let expected = concat!(
+            "#######\n",
+            "#~~~~~#\n",
+            "###~~~#\n",
+            "..#~~~#\n",
+            "..#~~~#\n",
+            "###~###\n",
+            "#~~~#..\n",
+            "##~~###\n",
+            ".#~~~~#\n",
+            ".######",
+        );
+        assert_eq!(drawing, expected);
+        assert!(super::show(&INPUT.into(), 2).is_err());
     }
 }
@@ -4,21 +4,23 @@
 //!
 //! [puzzle site](https://adventofcode.com/2024/day/1)
 
-use std::{error::Error, iter::zip};
+use crate::{PuzzleError, error::parse};
+use std::iter::zip;
 
-fn parse_lists(input: String) -> Result<[Vec<usize>; 2], Box<dyn Error>> {
+fn parse_lists(input: &crate::PuzzleInput) -> Result<[Vec<usize>; 2], PuzzleError> {
     let mut list1 = Vec::<usize>::new();
     let mut list2 = Vec::<usize>::new();
     for line in input.lines() {
         let mut split = line.split_whitespace();
-        list1.push(split.next().ok_or("empty line")?.parse()?);
-        list2.push(split.next().ok_or("no second id")?.parse()?);
+        let missing = || PuzzleError::parse(input, line, "expected two IDs");
+        list1.push(parse(input, split.next().ok_or_else(missing)?)?);
+        list2.push(parse(input, split.next().ok_or_else(missing)?)?);
     }
     Ok([list1, list2])
 }
 
 /// Part 1: Total sum of differences between sorted lists
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let [mut list1, mut list2] = parse_lists(input)?;
     list1.sort_unstable();
     list2.sort_unstable();
@@ -30,7 +32,7 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 }
 
 /// Part 2: Sum all products of list 1 IDs with their multiplicity in list 2
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let [list1, list2] = parse_lists(input)?;
     let mut sum = 0;
     for id1 in list1 {
@@ -39,17 +41,19 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(sum.to_string())
 }
 
+crate::register!(2024, 1, "Historian Hysteria", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "11");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "11");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "31");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "31");
     }
 }
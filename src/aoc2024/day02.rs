@@ -45,14 +45,16 @@ impl From<Vec<u8>> for Report {
     }
 }
 
-fn parse(line: &str) -> Result<Vec<u8>, std::num::ParseIntError> {
-    line.split_whitespace().map(|s| s.parse()).collect()
+fn parse(input: &crate::PuzzleInput, line: &str) -> Result<Vec<u8>, crate::PuzzleError> {
+    (line.split_whitespace())
+        .map(|s| crate::error::parse(input, s))
+        .collect()
 }
 
-fn solution(input: String, use_dampener: bool) -> crate::PuzzleResult {
+fn solution(input: &crate::PuzzleInput, use_dampener: bool) -> crate::PuzzleResult {
     let mut number_of_safe_reports = 0;
     for line in input.lines() {
-        let levels = parse(line)?;
+        let levels = parse(input, line)?;
         if Report::from(levels.clone()) != Report::Unsafe {
             number_of_safe_reports += 1;
         } else if use_dampener {
@@ -70,16 +72,18 @@ fn solution(input: String, use_dampener: bool) -> crate::PuzzleResult {
 }
 
 /// Part 1: Number of safe reports
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, false)
 }
 
 /// Part 2: Number of safe reports when up to one unsafe level can be ignored
 /// per report
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, true)
 }
 
+crate::register!(2024, 2, "Red-Nosed Reports", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -93,11 +97,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "2");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "2");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "4");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "4");
     }
 }
@@ -7,14 +7,19 @@
 //!
 //! [puzzle site](https://adventofcode.com/2024/day05)
 
-use std::{collections::HashMap, error::Error};
+use crate::{PuzzleError, error::parse};
+use std::collections::HashMap;
 
-fn parse_rules(rules: &str) -> Result<HashMap<u16, Vec<u16>>, Box<dyn Error>> {
+fn parse_rules(
+    input: &crate::PuzzleInput,
+    rules: &str,
+) -> Result<HashMap<u16, Vec<u16>>, PuzzleError> {
     let mut map = HashMap::new();
     for line in rules.lines() {
-        let (before, after) = line.split_once("|").ok_or("rule without |")?;
-        let after = after.parse()?;
-        map.entry(before.parse()?)
+        let (before, after) = (line.split_once("|"))
+            .ok_or_else(|| PuzzleError::parse(input, line, "rule without |"))?;
+        let after = parse(input, after)?;
+        map.entry(parse(input, before)?)
             .and_modify(|v: &mut Vec<_>| v.push(after))
             .or_insert(vec![after]);
     }
@@ -22,14 +27,15 @@ fn parse_rules(rules: &str) -> Result<HashMap<u16, Vec<u16>>, Box<dyn Error>> {
 }
 
 /// Part 1: Sum middle numbers over all correctly ordered sequences
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let (rules, sequences) = input.split_once("\n\n").ok_or("no blank line found")?;
-    let rules = parse_rules(rules)?;
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let (rules, sequences) = (input.split_once("\n\n"))
+        .ok_or_else(|| PuzzleError::MissingData("blank line after rules".to_owned()))?;
+    let rules = parse_rules(input, rules)?;
     let mut sum = 0;
     'sequences: for sequence in sequences.lines() {
         let mut numbers_before = Vec::new();
         for number in sequence.split(",") {
-            let number: u16 = number.parse()?;
+            let number: u16 = parse(input, number)?;
             if let Some(numbers_after) = rules.get(&number) {
                 for number_before in &numbers_before {
                     if numbers_after.contains(number_before) {
@@ -45,15 +51,16 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 }
 
 /// Part 1: Sum middle numbers over all fixed incorrectly ordered sequences
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let (rules, sequences) = input.split_once("\n\n").ok_or("no blank line found")?;
-    let rules = parse_rules(rules)?;
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let (rules, sequences) = (input.split_once("\n\n"))
+        .ok_or_else(|| PuzzleError::MissingData("blank line after rules".to_owned()))?;
+    let rules = parse_rules(input, rules)?;
     let mut sum = 0;
     for sequence in sequences.lines() {
         let mut numbers_before = Vec::new();
         let mut was_ordered = true;
         for number in sequence.split(",") {
-            let number: u16 = number.parse()?;
+            let number: u16 = parse(input, number)?;
             let mut insert_happened = false;
             if let Some(numbers_after) = rules.get(&number) {
                 for i in 0..numbers_before.len() {
@@ -76,6 +83,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(sum.to_string())
 }
 
+crate::register!(2024, 5, "Print Queue", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -111,11 +120,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "143");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "143");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "123");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "123");
     }
 }
@@ -6,32 +6,15 @@
 //!
 //! [puzzle site](https://adventofcode.com/2024/day06)
 
+use crate::geometry::Dir4::{self, *};
+use crate::visualize::{Frame, Visualize};
+use crate::{Grid, PuzzleError};
 use std::{collections::HashSet, str::FromStr};
 
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-enum Direction {
-    Right,
-    Up,
-    Left,
-    Down,
-}
-use Direction::*;
-
 #[derive(Clone, Copy, Eq, Hash, PartialEq)]
 struct Guard {
-    position: (usize, usize),
-    direction: Direction,
-}
-
-impl Guard {
-    fn turn_right(&mut self) {
-        self.direction = match self.direction {
-            Right => Down,
-            Up => Right,
-            Left => Up,
-            Down => Left,
-        }
-    }
+    position: [usize; 2],
+    direction: Dir4,
 }
 
 #[derive(Debug)]
@@ -41,106 +24,143 @@ enum Tile {
 }
 
 struct Map {
-    width: usize,
-    height: usize,
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<Tile>,
     guard: Guard,
 }
 
 impl FromStr for Map {
-    type Err = String;
+    type Err = PuzzleError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut tiles = Vec::new();
         let mut guard = None;
-        for (i, line) in s.lines().enumerate() {
-            let mut row = Vec::new();
-            for (j, symbol) in line.chars().enumerate() {
-                row.push(match symbol {
-                    '^' | '.' => Tile::Space,
-                    '#' => Tile::Obstruction,
-                    c => Err(format!("invalid character {c}"))?,
+        let tiles = Grid::parse_with(s, |position, symbol| {
+            if symbol == '^' {
+                guard = Some(Guard {
+                    position,
+                    direction: Up,
                 });
-                if symbol == '^' {
-                    guard = Some(Guard {
-                        position: (i, j),
-                        direction: Direction::Up,
-                    });
-                }
             }
-            tiles.push(row);
-        }
-        if !tiles.windows(2).all(|w| w[0].len() == w[1].len()) {
-            return Err("lines have different length".to_owned());
-        }
+            Some(match symbol {
+                '^' | '.' => Tile::Space,
+                '#' => Tile::Obstruction,
+                _ => None?,
+            })
+        })?;
         Ok(Self {
-            width: tiles[0].len(),
-            height: tiles.len(),
             tiles,
-            guard: guard.ok_or("no guard found")?,
+            guard: guard.ok_or_else(|| PuzzleError::MissingData("guard".to_owned()))?,
         })
     }
 }
 
 impl Map {
-    fn move_guard(&mut self) -> Result<(), ()> {
-        let (i, j) = self.guard.position;
-        let (i, j) = match self.guard.direction {
-            Right if j + 1 < self.width => (i, j + 1),
-            Up if i > 0 => (i - 1, j),
-            Left if j > 0 => (i, j - 1),
-            Down if i + 1 < self.height => (i + 1, j),
-            _ => Err(())?,
-        };
-        match self.tiles[i][j] {
-            Tile::Space => self.guard.position = (i, j),
+    fn move_guard(&mut self) -> Option<()> {
+        let position = (self.tiles).offset(self.guard.position, self.guard.direction.offset())?;
+        match self.tiles[position] {
+            Tile::Space => self.guard.position = position,
             Tile::Obstruction => {
-                self.guard.turn_right();
+                self.guard.direction = self.guard.direction.turn_right();
                 self.move_guard()?;
             }
         }
-        Ok(())
+        Some(())
+    }
+
+    /// Returns the map with the guard and marks (X) on some positions
+    fn draw(&self, marked: &HashSet<[usize; 2]>) -> Grid<char> {
+        let mut picture = self.tiles.map(|tile| match tile {
+            Tile::Space => '.',
+            Tile::Obstruction => '#',
+        });
+        for &position in marked {
+            picture[position] = 'X';
+        }
+        picture[self.guard.position] = self.guard.direction.to_arrow();
+        picture
     }
 }
 
-/// Part 1: Count all tiles the guard visited
-pub fn part1(input: String) -> crate::PuzzleResult {
+/// Solves a part emitting a frame for each move of the guard in part 1 and
+/// for each obstruction making the guard loop in part 2
+pub fn visualize(
+    input: &crate::PuzzleInput,
+    part: usize,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
+    if part == 1 {
+        count_visited(input, visualizer)
+    } else {
+        count_loop_obstructions(input, visualizer)
+    }
+}
+
+fn count_visited(
+    input: &crate::PuzzleInput,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
     let mut map: Map = input.parse()?;
     let mut seen_positions = HashSet::new();
     seen_positions.insert(map.guard.position);
-    while map.move_guard().is_ok() {
+    visualizer.frame(&|| Frame::new(map.draw(&seen_positions), "start"));
+    while map.move_guard().is_some() {
         seen_positions.insert(map.guard.position);
+        visualizer.frame(&|| {
+            let caption = format!("{} positions visited", seen_positions.len());
+            Frame::new(map.draw(&seen_positions), caption)
+        });
     }
     Ok(seen_positions.len().to_string())
 }
 
-/// Part 2: Count ways to make guard loop by adding one obstruction
-pub fn part2(input: String) -> crate::PuzzleResult {
+fn count_loop_obstructions(
+    input: &crate::PuzzleInput,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
     let mut map: Map = input.parse()?;
     let start = map.guard.position;
-    let mut count = 0;
-    for i in 0..map.height {
-        for j in 0..map.width {
-            match map.tiles[i][j] {
-                Tile::Space if (i, j) != start => map.tiles[i][j] = Tile::Obstruction,
-                _ => continue,
+    let mut loop_obstructions = HashSet::new();
+    for position in map.tiles.positions() {
+        match map.tiles[position] {
+            Tile::Space if position != start => map.tiles[position] = Tile::Obstruction,
+            _ => continue,
+        }
+        let mut visited_states = HashSet::new();
+        visited_states.insert(map.guard);
+        while map.move_guard().is_some() {
+            if !visited_states.insert(map.guard) {
+                loop_obstructions.insert(position);
+                break;
             }
-            let mut visited_states = HashSet::new();
-            visited_states.insert(map.guard);
-            while map.move_guard().is_ok() {
-                if !visited_states.insert(map.guard) {
-                    count += 1;
-                    break;
+        }
+        map.tiles[position] = Tile::Space;
+        map.guard.position = start;
+        map.guard.direction = Up;
+        if loop_obstructions.contains(&position) {
+            visualizer.frame(&|| {
+                let mut picture = map.draw(&HashSet::new());
+                for &obstruction in &loop_obstructions {
+                    picture[obstruction] = 'O';
                 }
-            }
-            map.tiles[i][j] = Tile::Space;
-            map.guard.position = start;
-            map.guard.direction = Up;
+                let count = loop_obstructions.len();
+                Frame::new(picture, format!("{count} obstructions make the guard loop"))
+            });
         }
     }
-    Ok(count.to_string())
+    Ok(loop_obstructions.len().to_string())
+}
+
+/// Part 1: Count all tiles the guard visited
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    count_visited(input, &mut ())
 }
 
+/// Part 2: Count ways to make guard loop by adding one obstruction
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    count_loop_obstructions(input, &mut ())
+}
+
+crate::register!(2024, 6, "Guard Gallivant", 1 => part1, 2 => part2, visualize => visualize);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -158,11 +178,30 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "41");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "41");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "6");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "6");
+    }
+
+    #[test]
+    fn test_visualize() {
+        let mut recording = crate::visualize::Recording::new();
+        super::visualize(&INPUT.into(), 1, &mut recording).unwrap();
+        let frames = recording.frames();
+        assert_eq!(
+            frames[0].picture.row(6).iter().collect::<String>(),
+            ".#..^....."
+        );
+        let last = frames.last().unwrap();
+        assert_eq!(last.caption, "41 positions visited");
+        assert_eq!(last.picture.row(9).iter().collect::<String>(), "......#v..");
+
+        let mut recording = crate::visualize::Recording::new();
+        super::visualize(&INPUT.into(), 2, &mut recording).unwrap();
+        let last = recording.frames().last().unwrap().picture.to_string();
+        assert_eq!(last.matches('O').count(), 6);
     }
 }
@@ -6,6 +6,8 @@
 //!
 //! [puzzle site](https://adventofcode.com/2024/day07)
 
+use crate::{PuzzleError, error::parse};
+
 fn is_valid_equation(result: u64, numbers: Vec<u64>, ops: &[fn(u64, u64) -> u64]) -> bool {
     if numbers.len() == 1 {
         return result == numbers[0];
@@ -20,12 +22,13 @@ fn is_valid_equation(result: u64, numbers: Vec<u64>, ops: &[fn(u64, u64) -> u64]
     false
 }
 
-fn solution(input: String, ops: &[fn(u64, u64) -> u64]) -> crate::PuzzleResult {
+fn solution(input: &crate::PuzzleInput, ops: &[fn(u64, u64) -> u64]) -> crate::PuzzleResult {
     let mut sum = 0;
     for line in input.lines() {
-        let (result, numbers) = line.split_once(": ").ok_or("no colon found")?;
-        let result = result.parse()?;
-        let numbers = (numbers.split_whitespace().map(|s| s.parse())).collect::<Result<_, _>>()?;
+        let (result, numbers) = (line.split_once(": "))
+            .ok_or_else(|| PuzzleError::parse(input, line, "no colon found"))?;
+        let result = parse(input, result)?;
+        let numbers = input.numbers_in(numbers)?;
         if is_valid_equation(result, numbers, ops) {
             sum += result;
         }
@@ -34,7 +37,7 @@ fn solution(input: String, ops: &[fn(u64, u64) -> u64]) -> crate::PuzzleResult {
 }
 
 /// Part 1: Combinations of addition and multiplication
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, &[|a, b| a * b, |a, b| a + b])
 }
 
@@ -43,10 +46,12 @@ fn concatenation(a: u64, b: u64) -> u64 {
 }
 
 /// Part 2: Combinations of addition, multiplication, and concatenation
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, &[|a, b| a * b, |a, b| a + b, concatenation])
 }
 
+crate::register!(2024, 7, "Bridge Repair", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -63,11 +68,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "3749");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "3749");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "11387");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "11387");
     }
 }
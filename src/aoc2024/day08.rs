@@ -19,10 +19,12 @@ struct Map {
 }
 
 impl FromStr for Map {
-    type Err = String;
+    type Err = crate::PuzzleError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let width = s.lines().next().ok_or("empty input")?.chars().count() as u8;
+        let first_line = s.lines().next();
+        let error = || crate::PuzzleError::MissingData("map".to_owned());
+        let width = first_line.ok_or_else(error)?.chars().count() as u8;
         let height = s.lines().count() as u8;
         let mut antennas = HashMap::new();
         for (i, line) in s.lines().enumerate() {
@@ -46,7 +48,7 @@ impl FromStr for Map {
 }
 
 /// Part 1: Antinodes are only found exactly opposite of the partner antenna
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let map: Map = input.parse()?;
     let mut antinodes = HashSet::new();
     for locations in map.antennas.values() {
@@ -80,7 +82,7 @@ fn cancel([mut a, mut b]: [i8; 2]) -> [i8; 2] {
 }
 
 /// Part 2: Antinodes are found everywhere on the line through both antennas
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let map: Map = input.parse()?;
     let mut antinodes = HashSet::new();
     for locations in map.antennas.values() {
@@ -105,6 +107,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(antinodes.len().to_string())
 }
 
+crate::register!(2024, 8, "Resonant Collinearity", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -124,11 +128,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "14");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "14");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "34");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "34");
     }
 }
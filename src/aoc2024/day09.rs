@@ -14,7 +14,7 @@ enum Section {
 }
 use Section::*;
 
-fn parse(input: String) -> (Vec<Section>, usize) {
+fn parse(input: &crate::PuzzleInput) -> (Vec<Section>, usize) {
     let mut sections = Vec::new();
     let mut number_of_files = 0;
     for (i, len) in input.chars().filter_map(|c| c.to_digit(10)).enumerate() {
@@ -49,7 +49,7 @@ fn checksum(sections: Vec<Section>) -> usize {
 
 /// Part 1: Occupied blocks are moved from the end to the first free block until
 /// no gaps remain
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let (mut sections, _) = parse(input);
     'move_file: loop {
         let (id, mut len) = loop {
@@ -81,7 +81,7 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 
 /// Part 2: Files are moved in order of decreasing ID to the first gap that fits
 /// the file
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let (mut sections, number_of_files) = parse(input);
     for id in (0..number_of_files).rev() {
         let (old_index, len) = (sections.iter().copied().enumerate())
@@ -105,17 +105,19 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(checksum(sections).to_string())
 }
 
+crate::register!(2024, 9, "Disk Fragmenter", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "2333133121414131402";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "1928");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "1928");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "2858");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "2858");
     }
 }
@@ -5,72 +5,17 @@
 //!
 //! [puzzle site](https://adventofcode.com/2024/day10)
 
-use std::{collections::VecDeque, str::FromStr};
+use std::collections::VecDeque;
 
-struct Map {
-    width: usize,
-    height: usize,
-    data: Vec<Vec<u32>>,
-}
-
-impl FromStr for Map {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let width = s.lines().next().ok_or("empty input")?.chars().count();
-        let height = s.lines().count();
-        let data = (s.lines())
-            .map(|l| l.chars().filter_map(|c| c.to_digit(10)).collect())
-            .collect();
-        Ok(Self {
-            width,
-            height,
-            data,
-        })
-    }
-}
-
-impl Map {
-    fn neighbors(&self, [i, j]: [usize; 2]) -> Vec<[usize; 2]> {
-        let mut neighbors = Vec::new();
-        if j + 1 < self.width {
-            neighbors.push([i, j + 1]);
-        }
-        if i > 0 {
-            neighbors.push([i - 1, j]);
-        }
-        if j > 0 {
-            neighbors.push([i, j - 1]);
-        }
-        if i + 1 < self.height {
-            neighbors.push([i + 1, j]);
-        }
-        neighbors
-    }
-
-    fn get(&self, [i, j]: [usize; 2]) -> u32 {
-        self.data[i][j]
-    }
-}
-
-fn solution(input: String, count_distinct: bool) -> crate::PuzzleResult {
-    let map: Map = input.parse()?;
-    let mut trailheads = Vec::new();
-    for i in 0..map.height {
-        for j in 0..map.width {
-            if map.data[i][j] == 0 {
-                trailheads.push([i, j]);
-            }
-        }
-    }
+fn solution(input: &crate::PuzzleInput, count_distinct: bool) -> crate::PuzzleResult {
+    let map = input.digit_grid()?;
     let mut sum = 0;
-    for [i, j] in trailheads {
-        let mut locations = VecDeque::from([[i, j]]);
+    for trailhead in map.find_all(&0) {
+        let mut locations = VecDeque::from([trailhead]);
         for target in 1..=9 {
             for _ in 0..locations.len() {
-                for neighbor in map.neighbors(locations.pop_front().unwrap()) {
-                    if map.get(neighbor) == target
-                        && (count_distinct || !locations.contains(&neighbor))
+                for neighbor in map.neighbors4(locations.pop_front().unwrap()) {
+                    if map[neighbor] == target && (count_distinct || !locations.contains(&neighbor))
                     {
                         locations.push_back(neighbor);
                     }
@@ -83,15 +28,17 @@ fn solution(input: String, count_distinct: bool) -> crate::PuzzleResult {
 }
 
 /// Part 1: Sum up how many 9s can be reached from each 0 over all 0s
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, false)
 }
 
 /// Part 2: Count all distinct trails
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, true)
 }
 
+crate::register!(2024, 10, "Hoof It", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -107,11 +54,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "36");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "36");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "81");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "81");
     }
 }
@@ -6,9 +6,11 @@
 //!
 //! [puzzle site](https://adventofcode.com/2024/day11)
 
-fn solution(input: String, iterations: u8) -> crate::PuzzleResult {
-    let mut numbers: Vec<u64> =
-        (input.split_whitespace().map(|n| n.parse())).collect::<Result<_, _>>()?;
+fn solution(input: &crate::PuzzleInput, iterations: u8) -> crate::PuzzleResult {
+    let mut numbers: Vec<u64> = (input
+        .split_whitespace()
+        .map(|n| crate::error::parse(input, n)))
+    .collect::<Result<_, _>>()?;
     let mut counts = vec![1u64; numbers.len()];
     for _ in 0..iterations {
         let mut i = 0;
@@ -45,21 +47,23 @@ fn solution(input: String, iterations: u8) -> crate::PuzzleResult {
 }
 
 /// Part 1: 25 Iterations
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, 25)
 }
 
 /// Part 2: 75 Iterations
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, 75)
 }
 
+crate::register!(2024, 11, "Plutonian Pebbles", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "125 17";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "55312");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "55312");
     }
 }
@@ -9,7 +9,7 @@
 //!
 //! [puzzle site](https://adventofcode.com/2024/day12)
 
-fn regions(input: String) -> Vec<Vec<[usize; 2]>> {
+fn regions(input: &crate::PuzzleInput) -> Vec<Vec<[usize; 2]>> {
     let mut plants: Vec<Vec<(char, usize)>> = Vec::new();
     let mut regions: Vec<Vec<[usize; 2]>> = Vec::new();
     for (i, line) in input.lines().enumerate() {
@@ -116,15 +116,17 @@ fn price(regions: Vec<Vec<[usize; 2]>>, with_discount: bool) -> usize {
 }
 
 /// Part 1: Without bulk discount
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     Ok(price(regions(input), false).to_string())
 }
 
 /// Part 2: With bulk discount
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     Ok(price(regions(input), true).to_string())
 }
 
+crate::register!(2024, 12, "Garden Groups", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const FIRST: &str = "AAAA\nBBCD\nBBCC\nEEEC";
@@ -146,17 +148,17 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(FIRST.to_string()).unwrap(), "140");
-        assert_eq!(&super::part1(SECOND.to_string()).unwrap(), "772");
-        assert_eq!(&super::part1(THIRD.to_string()).unwrap(), "1930");
+        assert_eq!(&super::part1(&FIRST.into()).unwrap(), "140");
+        assert_eq!(&super::part1(&SECOND.into()).unwrap(), "772");
+        assert_eq!(&super::part1(&THIRD.into()).unwrap(), "1930");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(FIRST.to_string()).unwrap(), "80");
-        assert_eq!(&super::part2(SECOND.to_string()).unwrap(), "436");
-        assert_eq!(&super::part2(THIRD.to_string()).unwrap(), "1206");
-        assert_eq!(&super::part2(FORTH.to_string()).unwrap(), "236");
-        assert_eq!(&super::part2(FIFTH.to_string()).unwrap(), "368");
+        assert_eq!(&super::part2(&FIRST.into()).unwrap(), "80");
+        assert_eq!(&super::part2(&SECOND.into()).unwrap(), "436");
+        assert_eq!(&super::part2(&THIRD.into()).unwrap(), "1206");
+        assert_eq!(&super::part2(&FORTH.into()).unwrap(), "236");
+        assert_eq!(&super::part2(&FIFTH.into()).unwrap(), "368");
     }
 }
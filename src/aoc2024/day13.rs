@@ -14,57 +14,116 @@
 //!
 //! [puzzle site](https://adventofcode.com/2024/day13)
 
-fn parse_line(line: &str, symbol: &str) -> Result<(i64, i64), Box<dyn std::error::Error>> {
-    let error = format!("invalid line: {line}");
-    let mut data = line
-        .split(": ")
-        .nth(1)
-        .ok_or(error.clone())?
-        .split(", ")
-        .map(|expr| expr.split(symbol).nth(1).ok_or(error.clone()));
-    let x = data.next().ok_or(error.clone())??.parse()?;
-    let y = data.next().ok_or(error)??.parse()?;
-    Ok((x, y))
+use num::Integer;
+
+use crate::{PuzzleError, math};
+
+const COSTS: [i64; 2] = [3, 1];
+
+/// Returns the lowest cost of pressing buttons moving the claw by `a` and `b`
+/// to reach the prize, pressing each button at most `limit` times if given
+fn lowest_cost(a: [i64; 2], b: [i64; 2], prize: [i64; 2], limit: Option<i64>) -> Option<i64> {
+    let presses = match math::solve_2x2([[a[0], b[0]], [a[1], b[1]]], prize) {
+        Some(presses) if presses.iter().all(|presses| presses.is_integer()) => {
+            presses.map(|presses| presses.to_integer())
+        }
+        Some(_) => None?,
+        None => collinear_presses(a, b, prize, limit)?,
+    };
+    let within_limit = |presses: &i64| *presses >= 0 && limit.is_none_or(|limit| *presses <= limit);
+    (presses.iter().all(within_limit)).then(|| COSTS[0] * presses[0] + COSTS[1] * presses[1])
+}
+
+/// Returns the cheapest presses of buttons moving the claw along the same line
+///
+/// The presses solving `a * presses[0] + b * presses[1] == prize` along the
+/// line lie on a line themselves on which the cost is linear, so the cheapest
+/// ones are at one of the ends bounded by zero presses or the limit.
+fn collinear_presses(
+    a: [i64; 2],
+    b: [i64; 2],
+    prize: [i64; 2],
+    limit: Option<i64>,
+) -> Option<[i64; 2]> {
+    let cross = |u: [i64; 2], v: [i64; 2]| u[0] * v[1] - u[1] * v[0];
+    if cross(a, prize) != 0 || cross(b, prize) != 0 {
+        return None;
+    }
+    let axis = if a[0] != 0 || b[0] != 0 {
+        0
+    } else if a[1] != 0 || b[1] != 0 {
+        1
+    } else {
+        // Neither button moves the claw
+        return (prize == [0, 0]).then_some([0, 0]);
+    };
+    let (presses, step) = math::diophantine(a[axis], b[axis], prize[axis])?;
+
+    // Bounds of k for presses + k * step being between zero and the limit
+    let (mut lowest, mut highest) = (None::<i64>, None::<i64>);
+    for button in 0..2 {
+        let mut constraints = vec![(presses[button], step[button])];
+        if let Some(limit) = limit {
+            constraints.push((limit - presses[button], -step[button]));
+        }
+        // Require constant + k * slope >= 0
+        for (constant, slope) in constraints {
+            match slope.signum() {
+                1 => {
+                    let bound = Integer::div_ceil(&-constant, &slope);
+                    lowest = Some(lowest.map_or(bound, |lowest| lowest.max(bound)));
+                }
+                -1 => {
+                    let bound = Integer::div_floor(&constant, &-slope);
+                    highest = Some(highest.map_or(bound, |highest| highest.min(bound)));
+                }
+                _ if constant < 0 => return None,
+                _ => (),
+            }
+        }
+    }
+    if let (Some(lowest), Some(highest)) = (lowest, highest)
+        && lowest > highest
+    {
+        return None;
+    }
+    let cost_slope = COSTS[0] * step[0] + COSTS[1] * step[1];
+    let k = if cost_slope > 0 {
+        lowest?
+    } else {
+        highest.or(lowest)?
+    };
+    Some([presses[0] + k * step[0], presses[1] + k * step[1]])
 }
 
-fn solution(input: String, fix_unit_conversion_error: bool) -> crate::PuzzleResult {
+fn solution(input: &crate::PuzzleInput, fix_unit_conversion_error: bool) -> crate::PuzzleResult {
     let mut price = 0;
-    for machine in input.split("\n\n") {
-        let mut lines = machine.lines();
-        let error = format!("machine with too few lines:\n{machine}");
-        let (dxa, dya) = parse_line(lines.next().ok_or(error.clone())?, "+")?;
-        let (dxb, dyb) = parse_line(lines.next().ok_or(error.clone())?, "+")?;
-        let (mut tx, mut ty) = parse_line(lines.next().ok_or(error.clone())?, "=")?;
+    for machine in input.blocks() {
+        let [dxa, dya, dxb, dyb, mut tx, mut ty] = input.numbers_in::<i64>(machine)?[..] else {
+            Err(PuzzleError::parse(input, machine, "expected six numbers"))?
+        };
         if fix_unit_conversion_error {
             tx += 10000000000000;
             ty += 10000000000000;
         }
-        let denominator = dxb * dya - dxa * dyb;
-        let numerator_a = dxb * ty - dyb * tx;
-        let numerator_b = dya * tx - dxa * ty;
-        if numerator_a % denominator == 0 && numerator_b % denominator == 0 {
-            let a = numerator_a / denominator;
-            let b = numerator_b / denominator;
-            if (0..=100).contains(&a) && (0..=100).contains(&b)
-                || fix_unit_conversion_error && a.is_positive() && b.is_positive()
-            {
-                price += 3 * a + b;
-            }
-        }
+        let limit = (!fix_unit_conversion_error).then_some(100);
+        price += lowest_cost([dxa, dya], [dxb, dyb], [tx, ty], limit).unwrap_or(0);
     }
     Ok(price.to_string())
 }
 
 /// Part 1: With unit conversion error
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, false)
 }
 
 /// Part 2: With unit conversion error
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, true)
 }
 
+crate::register!(2024, 13, "Claw Contraption", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -87,6 +146,40 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "480");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "480");
+    }
+
+    #[test]
+    fn test_collinear() {
+        let machine =
+            |a: &str, b: &str, prize: &str| format!("Button A: {a}\nButton B: {b}\nPrize: {prize}");
+        // Parallel buttons preferring the cheaper B button
+        let parallel = machine("X+2, Y+2", "X+3, Y+3", "X=9, Y=9");
+        assert_eq!(&super::part1(&parallel.as_str().into()).unwrap(), "3");
+        let parallel = machine("X+2, Y+2", "X+3, Y+3", "X=7, Y=7");
+        assert_eq!(&super::part1(&parallel.as_str().into()).unwrap(), "7");
+        let limited = machine("X+1, Y+1", "X+1, Y+1", "X=150, Y=150");
+        assert_eq!(&super::part1(&limited.as_str().into()).unwrap(), "250");
+        let parallel = machine("X+1, Y+1", "X+2, Y+2", "X=0, Y=0");
+        assert_eq!(
+            &super::part2(&parallel.as_str().into()).unwrap(),
+            "5000000000000"
+        );
+
+        // Anti-parallel buttons
+        let anti_parallel = machine("X+4, Y+2", "X+-2, Y+-1", "X=6, Y=3");
+        assert_eq!(&super::part1(&anti_parallel.as_str().into()).unwrap(), "7");
+        let anti_parallel = machine("X+-3, Y+0", "X+2, Y+0", "X=1, Y=0");
+        assert_eq!(&super::part1(&anti_parallel.as_str().into()).unwrap(), "5");
+
+        // Unreachable prizes
+        for unreachable in [
+            machine("X+2, Y+4", "X+4, Y+8", "X=3, Y=6"),
+            machine("X+2, Y+4", "X+4, Y+8", "X=4, Y=7"),
+            machine("X+2, Y+2", "X+3, Y+3", "X=1, Y=1"),
+            machine("X+0, Y+0", "X+0, Y+0", "X=1, Y=1"),
+        ] {
+            assert_eq!(&super::part1(&unreachable.as_str().into()).unwrap(), "0");
+        }
     }
 }
@@ -7,14 +7,10 @@
 //!
 //! [puzzle site](https://adventofcode.com/2024/day14)
 
-fn parse_vector(string: &str) -> Result<(i64, i64), Box<dyn std::error::Error>> {
-    let (j, i) = string
-        .split_once("=")
-        .and_then(|(_, numbers)| numbers.split_once(","))
-        .ok_or(format!("invalid vector: {string}"))?;
-    Ok((i.parse()?, j.parse()?))
-}
+use crate::visualize::{Frame, Visualize};
+use crate::{Grid, PuzzleError, math};
 
+#[derive(Clone)]
 struct Robots {
     number: usize,
     positions: Vec<(i64, i64)>,
@@ -35,6 +31,19 @@ impl Robots {
         }
     }
 
+    /// Returns the area with the number of robots on each tile or a dot if
+    /// there are none
+    fn draw(&self, width: i64, height: i64) -> Grid<char> {
+        let mut counts = Grid::new(width as usize, height as usize, 0);
+        for &(i, j) in &self.positions {
+            counts[[i as usize, j as usize]] += 1;
+        }
+        counts.map(|&count| match count {
+            0 => '.',
+            count => char::from_digit(count, 10).unwrap_or('*'),
+        })
+    }
+
     fn position_mean(&self) -> (f64, f64) {
         (
             self.positions.iter().map(|p| p.0 as f64).sum::<f64>() / self.number as f64,
@@ -59,18 +68,23 @@ impl Robots {
     }
 }
 
-impl std::str::FromStr for Robots {
-    type Err = Box<dyn std::error::Error>;
+impl TryFrom<&crate::PuzzleInput> for Robots {
+    type Error = PuzzleError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn try_from(input: &crate::PuzzleInput) -> Result<Self, Self::Error> {
         let mut number = 0;
         let mut positions = Vec::new();
         let mut velocities = Vec::new();
-        for line in s.lines() {
-            let (position, velocity) =
-                (line.split_once(" ")).ok_or(format!("invalid line: {line}"))?;
-            positions.push(parse_vector(position)?);
-            velocities.push(parse_vector(velocity)?);
+        for line in input.lines() {
+            let [pj, pi, vj, vi] = input.numbers_in(line)?[..] else {
+                Err(PuzzleError::parse(
+                    input,
+                    line,
+                    "expected position and velocity",
+                ))?
+            };
+            positions.push((pi, pj));
+            velocities.push((vi, vj));
             number += 1;
         }
         Ok(Self {
@@ -81,32 +95,18 @@ impl std::str::FromStr for Robots {
     }
 }
 
-impl std::fmt::Display for Robots {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let i_max = self.positions.iter().map(|p| p.0).max().unwrap_or_default();
-        let j_max = self.positions.iter().map(|p| p.1).max().unwrap_or_default();
-        for i in 0..=i_max {
-            for j in 0..=j_max {
-                f.write_str(
-                    &match self.positions.iter().filter(|p| **p == (i, j)).count() {
-                        0 => " ".to_owned(),
-                        n => n.to_string(),
-                    },
-                )?;
-            }
-            if i < i_max {
-                use std::fmt::Write;
-                f.write_char('\n')?;
-            }
-        }
-        Ok(())
-    }
-}
-
-fn part1_with_dimensions(input: String, width: i64, height: i64) -> crate::PuzzleResult {
-    let mut robots: Robots = input.parse()?;
-    for _ in 0..100 {
+fn part1_with_dimensions(
+    input: &crate::PuzzleInput,
+    width: i64,
+    height: i64,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
+    let mut robots = Robots::try_from(input)?;
+    visualizer.frame(&|| Frame::new(robots.draw(width, height), "after 0 seconds"));
+    for time in 1..=100 {
         robots.update(width, height);
+        visualizer
+            .frame(&|| Frame::new(robots.draw(width, height), format!("after {time} seconds")));
     }
     let mut counts = [0; 4];
     for position in robots.positions {
@@ -121,15 +121,22 @@ fn part1_with_dimensions(input: String, width: i64, height: i64) -> crate::Puzzl
     Ok(counts.into_iter().product::<usize>().to_string())
 }
 
-/// Part 1: Product of robot numbers in each quadrant after 100 iterations
-pub fn part1(input: String) -> crate::PuzzleResult {
-    part1_with_dimensions(input, 101, 103)
-}
-
-/// Part 2: Number of iterations until the robots display a tree
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let (width, height) = (101i64, 103i64);
-    let mut robots: Robots = input.parse()?;
+/// Returns the number of iterations until the robots display a tree emitting
+/// a frame for each time the robots are aligned vertically before
+///
+/// Robots return to their positions after a number of iterations equal to
+/// the width horizontally and the height vertically. They display the tree
+/// when the variance of their positions is smallest in both directions, so
+/// the time is found by the Chinese remainder theorem from the times of least
+/// variance within one period each.
+fn tree_time(
+    input: &crate::PuzzleInput,
+    width: i64,
+    height: i64,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
+    let initial = Robots::try_from(input)?;
+    let mut robots = initial.clone();
     let mut min_i_var = height.pow(2) as f64;
     let mut min_i_var_time = 0;
     let mut min_j_var = width.pow(2) as f64;
@@ -146,17 +153,44 @@ pub fn part2(input: String) -> crate::PuzzleResult {
         }
         robots.update(width, height);
     }
-    for leap in 0.. {
-        let offset = width * leap + min_j_var_time - min_i_var_time;
-        if offset % height == 0 {
-            // robots.update_with_time(width, height, min_i_var_time + offset - width.max(height));
-            // println!("{robots}");
-            return Ok((min_i_var_time + offset).to_string());
-        }
+    let (tree_time, _) = (math::crt([(min_i_var_time, height), (min_j_var_time, width)]))
+        .ok_or_else(|| PuzzleError::NoSolution("robots never align".to_owned()))?;
+    for time in (min_i_var_time..=tree_time).step_by(height as usize) {
+        visualizer.frame(&|| {
+            let mut robots = initial.clone();
+            robots.update_with_time(width, height, time);
+            Frame::new(robots.draw(width, height), format!("after {time} seconds"))
+        });
+    }
+    Ok(tree_time.to_string())
+}
+
+/// Solves a part emitting a frame for every second in part 1 and for every
+/// time the robots are aligned vertically in part 2
+pub fn visualize(
+    input: &crate::PuzzleInput,
+    part: usize,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
+    if part == 1 {
+        part1_with_dimensions(input, 101, 103, visualizer)
+    } else {
+        tree_time(input, 101, 103, visualizer)
     }
-    Err("no solution found")?
 }
 
+/// Part 1: Product of robot numbers in each quadrant after 100 iterations
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    visualize(input, 1, &mut ())
+}
+
+/// Part 2: Number of iterations until the robots display a tree
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    visualize(input, 2, &mut ())
+}
+
+crate::register!(2024, 14, "Restroom Redoubt", 1 => part1, 2 => part2, visualize => visualize);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -177,8 +211,38 @@ mod tests {
     #[test]
     fn test_part1() {
         assert_eq!(
-            &super::part1_with_dimensions(INPUT.to_string(), 11, 7).unwrap(),
+            &super::part1_with_dimensions(&INPUT.into(), 11, 7, &mut ()).unwrap(),
             "12"
         );
     }
+
+    #[test]
+    fn test_tree_time() {
+        // Robots meeting at column 1 and row 4 after 23 seconds
+        let input = "p=3,2 v=1,1\np=0,5 v=2,3\np=4,0 v=-1,2\np=2,1 v=3,-2";
+        let mut recording = crate::visualize::Recording::new();
+        let time = super::tree_time(&input.into(), 5, 7, &mut recording).unwrap();
+        assert_eq!(&time, "23");
+        let frames = recording.frames();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[3].caption, "after 23 seconds");
+        assert_eq!(frames[3].picture[[4, 1]], '4');
+    }
+
+    #[test]
+    fn test_visualize() {
+        let mut recording = crate::visualize::Recording::new();
+        super::part1_with_dimensions(&INPUT.into(), 11, 7, &mut recording).unwrap();
+        let frames = recording.frames();
+        assert_eq!(frames.len(), 101);
+        assert_eq!(
+            frames[0].picture.to_string().lines().next(),
+            Some("1.12.......")
+        );
+        assert_eq!(frames[100].caption, "after 100 seconds");
+        assert_eq!(
+            frames[100].picture.row(0),
+            "......2..1.".chars().collect::<Vec<_>>()
+        );
+    }
 }
@@ -11,30 +11,22 @@
 //! outer wall summed over all boxes after applying the instructions.
 //!
 //! [puzzle site](https://adventofcode.com/2024/day15)
-#[derive(Clone, Copy)]
-enum Instruction {
-    Right,
-    Up,
-    Left,
-    Down,
-}
 
+use crate::geometry::Dir4;
+use crate::visualize::{Frame, Visualize};
+use crate::{Grid, PuzzleError};
 use std::collections::HashSet;
 
-use Instruction::*;
-
-impl TryFrom<char> for Instruction {
-    type Error = String;
-
-    fn try_from(c: char) -> Result<Self, Self::Error> {
-        Ok(match c {
-            '>' => Right,
-            '^' => Up,
-            '<' => Left,
-            'v' => Down,
-            _ => return Err(format!("{c} is no direction")),
+fn parse_instructions<'a>(
+    input: &'a str,
+    instructions: &'a str,
+) -> impl Iterator<Item = Result<Dir4, PuzzleError>> + 'a {
+    instructions.lines().flat_map(move |line| {
+        line.char_indices().map(move |(index, c)| {
+            Dir4::from_arrow(c)
+                .ok_or_else(|| PuzzleError::parse(input, &line[index..], "not a direction"))
         })
-    }
+    })
 }
 
 struct Warehouse {
@@ -50,16 +42,12 @@ struct Warehouse {
 impl Warehouse {
     fn apply_instruction_to_location(
         &self,
-        instruction: Instruction,
+        instruction: Dir4,
         [i, j]: [usize; 2],
     ) -> Option<[usize; 2]> {
-        Some(match instruction {
-            Right if j + 1 < self.width => [i, j + 1],
-            Up if i > 0 => [i - 1, j],
-            Left if j > 0 => [i, j - 1],
-            Down if i + 1 < self.height => [i + 1, j],
-            _ => return None,
-        })
+        let [di, dj] = instruction.offset();
+        let location = [i.checked_add_signed(di)?, j.checked_add_signed(dj)?];
+        (location[0] < self.height && location[1] < self.width).then_some(location)
     }
 
     fn contains_box(&self, [i, j]: [usize; 2]) -> Option<[usize; 2]> {
@@ -73,18 +61,21 @@ impl Warehouse {
         None
     }
 
-    fn move_boxes(&mut self, boxes: HashSet<[usize; 2]>, instruction: Instruction) -> bool {
+    fn move_boxes(&mut self, boxes: HashSet<[usize; 2]>, instruction: Dir4) -> bool {
         if boxes.is_empty() {
             return true;
         }
         let mut other_boxes = HashSet::new();
         for &[i, j] in &boxes {
             for dj in 0..self.box_width {
-                let location = self.apply_instruction_to_location(instruction, [i, j + dj]);
-                if location.is_none() || self.walls.contains(&location.unwrap()) {
+                let Some(location) = self.apply_instruction_to_location(instruction, [i, j + dj])
+                else {
+                    return false;
+                };
+                if self.walls.contains(&location) {
                     return false;
                 }
-                if let Some(other_box) = self.contains_box(location.unwrap())
+                if let Some(other_box) = self.contains_box(location)
                     && other_box != [i, j]
                 {
                     other_boxes.insert(other_box);
@@ -93,20 +84,18 @@ impl Warehouse {
         }
         if self.move_boxes(other_boxes, instruction) {
             for location in boxes {
-                if !self.boxes.remove(&location) {
-                    panic!("{location:?} is not a box")
-                }
-                self.boxes.insert(
-                    self.apply_instruction_to_location(instruction, location)
-                        .unwrap(),
-                );
+                let removed = self.boxes.remove(&location);
+                debug_assert!(removed, "only boxes are moved");
+                let moved = (self.apply_instruction_to_location(instruction, location))
+                    .expect("boxes are only moved after checking they stay inside");
+                self.boxes.insert(moved);
             }
             return true;
         }
         false
     }
 
-    fn instruct(&mut self, instruction: Instruction) {
+    fn instruct(&mut self, instruction: Dir4) {
         if let Some(location) = self.apply_instruction_to_location(instruction, self.robot)
             && !self.walls.contains(&location)
             && !self.contains_box(location).is_some_and(|box_location| {
@@ -117,6 +106,42 @@ impl Warehouse {
         }
     }
 
+    /// Doubles the width of everything except the robot
+    fn widen(&mut self) {
+        self.width *= 2;
+        self.robot[1] *= 2;
+        self.walls = (self.walls.iter())
+            .flat_map(|&[i, j]| [[i, 2 * j], [i, 2 * j + 1]])
+            .collect();
+        self.boxes = (self.boxes.iter()).map(|&[i, j]| [i, 2 * j]).collect();
+        self.box_width = 2;
+        self.outer_wall_thickness[1] = 2;
+    }
+
+    /// Returns the warehouse drawn like in the input with wide boxes ([])
+    fn draw(&self) -> Grid<char> {
+        let [di, dj] = self.outer_wall_thickness;
+        let mut picture = Grid::new(self.width + 2 * dj, self.height + 2 * di, '#');
+        for i in 0..self.height {
+            for j in 0..self.width {
+                picture[[i + di, j + dj]] = '.';
+            }
+        }
+        for &[i, j] in &self.walls {
+            picture[[i + di, j + dj]] = '#';
+        }
+        for &[i, j] in &self.boxes {
+            if self.box_width == 1 {
+                picture[[i + di, j + dj]] = 'O';
+            } else {
+                picture[[i + di, j + dj]] = '[';
+                picture[[i + di, j + dj + 1]] = ']';
+            }
+        }
+        picture[[self.robot[0] + di, self.robot[1] + dj]] = '@';
+        picture
+    }
+
     fn result(&self) -> usize {
         let [di, dj] = self.outer_wall_thickness;
         self.boxes
@@ -127,13 +152,14 @@ impl Warehouse {
 }
 
 impl std::str::FromStr for Warehouse {
-    type Err = String;
+    type Err = PuzzleError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let width = (s.lines().next())
             .and_then(|l| l.chars().count().checked_sub(2))
-            .ok_or("input width too small")?;
-        let height = (s.lines().count().checked_sub(2)).ok_or("input height too small")?;
+            .ok_or_else(|| PuzzleError::UnsupportedInput("warehouse too narrow".to_owned()))?;
+        let height = (s.lines().count().checked_sub(2))
+            .ok_or_else(|| PuzzleError::UnsupportedInput("warehouse too low".to_owned()))?;
         let mut robot = None;
         let mut walls = HashSet::new();
         let mut boxes = HashSet::new();
@@ -141,7 +167,7 @@ impl std::str::FromStr for Warehouse {
             if [0, height + 1].contains(&i) {
                 continue;
             }
-            for (j, character) in line.chars().enumerate() {
+            for (j, (index, character)) in line.char_indices().enumerate() {
                 if [0, width + 1].contains(&j) {
                     continue;
                 }
@@ -150,7 +176,7 @@ impl std::str::FromStr for Warehouse {
                         if robot.is_none() {
                             robot = Some([i - 1, j - 1])
                         } else {
-                            return Err("more than one robot".to_owned());
+                            return Err(PuzzleError::parse(s, &line[index..], "second robot"));
                         }
                     }
                     '#' => {
@@ -166,7 +192,7 @@ impl std::str::FromStr for Warehouse {
         Ok(Self {
             width,
             height,
-            robot: robot.ok_or("no robot found")?,
+            robot: robot.ok_or_else(|| PuzzleError::MissingData("robot".to_owned()))?,
             walls,
             boxes,
             box_width: 1,
@@ -175,34 +201,42 @@ impl std::str::FromStr for Warehouse {
     }
 }
 
-/// Part 1
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let (warehouse, instructions) = input.split_once("\n\n").ok_or("no blank line found")?;
+/// Solves a part emitting a frame for each instruction
+pub fn visualize(
+    input: &crate::PuzzleInput,
+    part: usize,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
+    let (warehouse, instructions) = (input.split_once("\n\n"))
+        .ok_or_else(|| PuzzleError::MissingData("blank line after warehouse".to_owned()))?;
     let mut warehouse: Warehouse = warehouse.parse()?;
-    for instruction in instructions.lines().flat_map(|l| l.chars()) {
-        warehouse.instruct(instruction.try_into()?);
+    if part == 2 {
+        warehouse.widen();
+    }
+    visualizer.frame(&|| Frame::new(warehouse.draw(), "start"));
+    for (step, instruction) in parse_instructions(input, instructions).enumerate() {
+        let instruction = instruction?;
+        warehouse.instruct(instruction);
+        visualizer.frame(&|| {
+            let caption = format!("move {}: {}", step + 1, instruction.to_arrow());
+            Frame::new(warehouse.draw(), caption)
+        });
     }
     Ok(warehouse.result().to_string())
 }
 
+/// Part 1
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    visualize(input, 1, &mut ())
+}
+
 /// Part 2
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let (warehouse, instructions) = input.split_once("\n\n").ok_or("no blank line found")?;
-    let mut warehouse: Warehouse = warehouse.parse()?;
-    warehouse.width *= 2;
-    warehouse.robot[1] *= 2;
-    warehouse.walls = (warehouse.walls.iter())
-        .flat_map(|&[i, j]| [[i, 2 * j], [i, 2 * j + 1]])
-        .collect();
-    warehouse.boxes = (warehouse.boxes.iter()).map(|&[i, j]| [i, 2 * j]).collect();
-    warehouse.box_width = 2;
-    warehouse.outer_wall_thickness[1] = 2;
-    for instruction in instructions.lines().flat_map(|l| l.chars()) {
-        warehouse.instruct(instruction.try_into()?);
-    }
-    Ok(warehouse.result().to_string())
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    visualize(input, 2, &mut ())
 }
 
+crate::register!(2024, 15, "Warehouse Woes", 1 => part1, 2 => part2, visualize => visualize);
+
 #[cfg(test)]
 mod tests {
     const SMALL: &str = concat!(
@@ -244,12 +278,34 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(SMALL.to_string()).unwrap(), "2028");
-        assert_eq!(&super::part1(BIG.to_string()).unwrap(), "10092");
+        assert_eq!(&super::part1(&SMALL.into()).unwrap(), "2028");
+        assert_eq!(&super::part1(&BIG.into()).unwrap(), "10092");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(BIG.to_string()).unwrap(), "9021");
+        assert_eq!(&super::part2(&BIG.into()).unwrap(), "9021");
+    }
+
+    #[test]
+    fn test_visualize() {
+        let mut recording = crate::visualize::Recording::new();
+        super::visualize(&BIG.into(), 2, &mut recording).unwrap();
+        let frames = recording.frames();
+        assert_eq!(frames.len(), 701);
+        assert_eq!(frames[1].caption, "move 1: <");
+        let first_row = "####################";
+        assert_eq!(
+            frames[0].picture.row(0).iter().collect::<String>(),
+            first_row
+        );
+        assert_eq!(
+            frames[0].picture.row(4).iter().collect::<String>(),
+            "##....[]@.....[]..##"
+        );
+        assert_eq!(
+            frames[700].picture.row(1).iter().collect::<String>(),
+            "##[].......[].[][]##"
+        );
     }
 }
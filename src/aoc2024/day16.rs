@@ -7,6 +7,12 @@
 //!
 //! [puzzle site](https://adventofcode.com/2024/day16)
 
+use std::collections::HashSet;
+
+use crate::geometry::Dir4;
+use crate::search::{self, ShortestPaths};
+use crate::{Grid, PuzzleError};
+
 #[derive(PartialEq)]
 enum Tile {
     Empty,
@@ -15,92 +21,51 @@ enum Tile {
 use Tile::*;
 
 impl Tile {
-    fn try_from_char(character: char) -> Result<Self, String> {
-        Ok(match character {
+    fn try_from_char(character: char) -> Option<Self> {
+        Some(match character {
             '.' | 'S' | 'E' => Self::Empty,
             '#' => Self::Wall,
-            _ => Err(format!("{character} is not a valid tile"))?,
+            _ => None?,
         })
     }
 }
 
 struct Maze {
-    width: usize,
-    height: usize,
-    map: Vec<Vec<Tile>>,
+    map: Grid<Tile>,
     start: [usize; 2],
     end: [usize; 2],
 }
 
 impl std::str::FromStr for Maze {
-    type Err = String;
+    type Err = PuzzleError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut map = Vec::new();
         let mut start = None;
         let mut end = None;
-        for (i, line) in s.lines().enumerate() {
-            let mut row = Vec::new();
-            for (j, character) in line.chars().enumerate() {
-                if character == 'S' {
-                    start = Some([i, j]);
-                }
-                if character == 'E' {
-                    end = Some([i, j]);
-                }
-                row.push(Tile::try_from_char(character)?);
+        let map = Grid::parse_with(s, |position, character| {
+            match character {
+                'S' => start = Some(position),
+                'E' => end = Some(position),
+                _ => (),
             }
-            map.push(row);
-        }
+            Tile::try_from_char(character)
+        })?;
         Ok(Self {
-            width: map[0].len(),
-            height: map.len(),
             map,
-            start: start.ok_or("no start found".to_owned())?,
-            end: end.ok_or("no end found".to_owned())?,
+            start: start.ok_or_else(|| PuzzleError::MissingData("start".to_owned()))?,
+            end: end.ok_or_else(|| PuzzleError::MissingData("end".to_owned()))?,
         })
     }
 }
 
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-enum Direction {
-    East,
-    North,
-    West,
-    South,
-}
-use Direction::*;
-
-impl Direction {
-    fn neighbors(&self) -> [Direction; 2] {
-        match self {
-            East => [South, North],
-            North => [East, West],
-            West => [North, South],
-            South => [West, East],
-        }
-    }
-}
-
-impl From<Direction> for usize {
-    fn from(direction: Direction) -> Self {
-        match direction {
-            East => 0,
-            North => 1,
-            West => 2,
-            South => 3,
-        }
-    }
-}
-
 #[derive(Copy, Clone, Eq, Hash, PartialEq)]
 struct MazeState {
     position: [usize; 2],
-    direction: Direction,
+    direction: Dir4,
 }
 
-impl From<([usize; 2], Direction)> for MazeState {
-    fn from((position, direction): ([usize; 2], Direction)) -> Self {
+impl From<([usize; 2], Dir4)> for MazeState {
+    fn from((position, direction): ([usize; 2], Dir4)) -> Self {
         Self {
             position,
             direction,
@@ -110,29 +75,23 @@ impl From<([usize; 2], Direction)> for MazeState {
 
 impl Maze {
     fn initial_state(&self) -> MazeState {
-        (self.start, East).into()
+        (self.start, Dir4::Right).into()
     }
 
     fn step(
         &self,
         MazeState {
-            position: [i, j],
+            position,
             direction,
         }: MazeState,
     ) -> Option<MazeState> {
-        let position = match direction {
-            East if j + 1 < self.width && self.map[i][j + 1] == Empty => [i, j + 1],
-            North if i > 0 && self.map[i - 1][j] == Empty => [i - 1, j],
-            West if j > 0 && self.map[i][j - 1] == Empty => [i, j - 1],
-            South if i + 1 < self.height && self.map[i + 1][j] == Empty => [i + 1, j],
-            _ => return None,
-        };
-        Some((position, direction).into())
+        let position = self.map.offset(position, direction.offset())?;
+        (self.map[position] == Empty).then(|| (position, direction).into())
     }
 
     fn neighbor_states(&self, state: MazeState) -> Vec<(MazeState, usize)> {
         let mut neighbors = Vec::from(
-            (state.direction.neighbors())
+            [state.direction.turn_right(), state.direction.turn_left()]
                 .map(|direction| ((state.position, direction).into(), 1000)),
         );
         if let Some(state) = self.step(state) {
@@ -142,99 +101,70 @@ impl Maze {
     }
 }
 
-struct Scores(Vec<Vec<[usize; 4]>>);
-
-impl Scores {
-    fn get(&self, state: MazeState) -> usize {
-        self.0[state.position[0]][state.position[1]][usize::from(state.direction)]
-    }
-
-    fn set(&mut self, state: MazeState, score: usize) {
-        self.0[state.position[0]][state.position[1]][usize::from(state.direction)] = score;
-    }
+/// Returns the lowest score paths from the start to any direction at the end
+fn lowest_score_paths(maze: &Maze) -> ShortestPaths<MazeState, usize> {
+    search::dijkstra(
+        [maze.initial_state()],
+        |&state| maze.neighbor_states(state),
+        |state| state.position == maze.end,
+    )
 }
 
-enum Task {
-    LowestScore,
-    CountTiles,
+/// Returns the tiles on lowest score paths
+fn best_tiles(paths: &ShortestPaths<MazeState, usize>) -> HashSet<[usize; 2]> {
+    (paths.on_shortest_paths(paths.goals()).into_iter())
+        .map(|state| state.position)
+        .collect()
 }
 
-fn solution(input: String, task: Task) -> crate::PuzzleResult {
+/// Part 1: Lowest possible score
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let maze: Maze = input.parse()?;
-    let mut scores = Scores(vec![vec![[usize::MAX; 4]; maze.width]; maze.height]);
-    let mut to_visit = std::collections::HashSet::new();
-    let mut predecessors = std::collections::HashMap::<MazeState, Vec<MazeState>>::new();
-    let mut lowest_score = None;
-    let start = maze.initial_state();
-    scores.set(start, 0);
-    to_visit.insert(start);
-    'dijkstra: loop {
-        let state = to_visit
-            .iter()
-            .min_by_key(|&&state| scores.get(state))
-            .copied()
-            .unwrap();
-        to_visit.remove(&state);
-        let score = scores.get(state);
-
-        for (neighbor, transition_score) in maze.neighbor_states(state) {
-            let neighbor_score = score + transition_score;
+    (lowest_score_paths(&maze).goal_distance())
+        .map(|score| score.to_string())
+        .ok_or_else(|| PuzzleError::NoSolution("no path to the end".to_owned()))
+}
 
-            if let Some(lowest_score) = lowest_score {
-                if neighbor_score > lowest_score {
-                    break 'dijkstra;
-                }
-            } else if neighbor.position == maze.end {
-                if let Task::LowestScore = task {
-                    return Ok(neighbor_score.to_string());
-                }
-                lowest_score = Some(neighbor_score);
-            }
+/// Part 2: Number of tiles part of a lowest score path
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    both(input).1
+}
 
-            let old_neighbor_score = scores.get(neighbor);
-            if neighbor_score <= old_neighbor_score {
-                if old_neighbor_score == usize::MAX {
-                    to_visit.insert(neighbor);
-                }
-                if neighbor_score == old_neighbor_score {
-                    predecessors.get_mut(&neighbor).unwrap().push(state);
-                } else {
-                    predecessors.insert(neighbor, vec![state]);
-                }
-                scores.set(neighbor, neighbor_score);
-            }
-        }
-    }
+/// Both parts from one search
+pub fn both(input: &crate::PuzzleInput) -> (crate::PuzzleResult, crate::PuzzleResult) {
+    crate::both_results(input.parse().and_then(|maze: Maze| {
+        let paths = lowest_score_paths(&maze);
+        let score = (paths.goal_distance())
+            .ok_or_else(|| PuzzleError::NoSolution("no path to the end".to_owned()))?;
+        Ok([score, best_tiles(&paths).len()])
+    }))
+}
 
-    let mut lowest_score_path_tiles = std::collections::HashSet::new();
-    let mut to_visit = Vec::new();
-    for direction in [East, North, West, South] {
-        to_visit.push(MazeState {
-            position: maze.end,
-            direction,
-        });
-    }
-    while let Some(state) = to_visit.pop() {
-        lowest_score_path_tiles.insert(state.position);
-        if let Some(predecessors) = predecessors.get(&state) {
-            for &predecessor in predecessors {
-                to_visit.push(predecessor);
-            }
+/// Maze with a lowest score path drawn with arrows for part 1 or all tiles on
+/// any lowest score path marked with O for part 2
+pub fn show(input: &crate::PuzzleInput, part: usize) -> crate::PuzzleResult {
+    let maze: Maze = input.parse()?;
+    let paths = lowest_score_paths(&maze);
+    let goal = (paths.goals().first())
+        .ok_or_else(|| PuzzleError::NoSolution("no path to the end".to_owned()))?;
+    let mut drawing = maze.map.map(|tile| if *tile == Wall { '#' } else { '.' });
+    drawing[maze.start] = 'S';
+    drawing[maze.end] = 'E';
+    if part == 1 {
+        let mut path: Vec<_> = (paths.path(goal).into_iter().flatten())
+            .map(|state| state.position)
+            .collect();
+        path.dedup();
+        drawing.draw_path(&path);
+    } else {
+        for position in best_tiles(&paths) {
+            drawing[position] = 'O';
         }
     }
-
-    Ok(lowest_score_path_tiles.len().to_string())
-}
-
-/// Part 1: Lowest possible score
-pub fn part1(input: String) -> crate::PuzzleResult {
-    solution(input, Task::LowestScore)
+    Ok(drawing.to_string())
 }
 
-/// Part 2: Number of tiles part of a lowest score path
-pub fn part2(input: String) -> crate::PuzzleResult {
-    solution(input, Task::CountTiles)
-}
+crate::register!(2024, 16, "Reindeer Maze", 1 => part1, 2 => part2, both => both, show => show);
 
 #[cfg(test)]
 mod tests {
@@ -278,13 +208,32 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(FIRST.to_string()).unwrap(), "7036");
-        assert_eq!(&super::part1(SECOND.to_string()).unwrap(), "11048");
+        assert_eq!(&super::part1(&FIRST.into()).unwrap(), "7036");
+        assert_eq!(&super::part1(&SECOND.into()).unwrap(), "11048");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(FIRST.to_string()).unwrap(), "45");
-        assert_eq!(&super::part2(SECOND.to_string()).unwrap(), "64");
+        assert_eq!(&super::part2(&FIRST.into()).unwrap(), "45");
+        assert_eq!(&super::part2(&SECOND.into()).unwrap(), "64");
+    }
+
+    #[test]
+    fn test_both() {
+        let (part1, part2) = super::both(&FIRST.into());
+        assert_eq!(
+            (part1.unwrap(), part2.unwrap()),
+            ("7036".into(), "45".into())
+        );
+    }
+
+    #[test]
+    fn test_show() {
+        let drawing = super::show(&FIRST.into(), 1).unwrap();
+        assert!(drawing.lines().nth(13).unwrap().starts_with("#^.."));
+        assert_eq!(drawing.matches(['^', '>', 'v', '<']).count(), 36);
+        let drawing = super::show(&SECOND.into(), 2).unwrap();
+        assert_eq!(drawing.matches('O').count(), 64);
+        assert_eq!(drawing.lines().nth(1), Some("#...#...#...#..O#"));
     }
 }
@@ -18,11 +18,26 @@
 //! [puzzle site](https://adventofcode.com/2024/day17) or the
 //! [implementation](../../../src/advent_of_code/aoc2024/day17.rs.html).
 
-use std::{error::Error, str::FromStr};
+use crate::vm::{self, Effect, Machine, Registers};
+use crate::{PuzzleError, error::parse};
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+use std::str::FromStr;
 
 use itertools::Itertools;
 
-#[derive(Clone, Copy)]
+/// Indices of the registers A, B and C
+const A: usize = 0;
+const B: usize = 1;
+const C: usize = 2;
+
+/// Number of instructions after which a program is taken to run forever
+const STEP_LIMIT: usize = 10_000_000;
+
+/// Number of instructions after which tracing a program stops
+const TRACE_STEP_LIMIT: usize = 100_000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum Instruction {
     Adv,
     Bxl,
@@ -35,6 +50,14 @@ enum Instruction {
 }
 use Instruction::*;
 
+impl Instruction {
+    /// Returns whether the operand is a combo operand which may refer to a
+    /// register
+    fn takes_combo(self) -> bool {
+        matches!(self, Adv | Bst | Out | Bdv | Cdv)
+    }
+}
+
 impl FromStr for Instruction {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -47,91 +70,323 @@ impl FromStr for Instruction {
             "5" => Out,
             "6" => Bdv,
             "7" => Cdv,
-            _ => Err(format!("invalid opcode: {s}"))?,
+            _ => Err("invalid opcode".to_owned())?,
         })
     }
 }
 
+/// Instruction together with its operand
+#[derive(Clone, Copy, Debug)]
+struct Command {
+    instruction: Instruction,
+    operand: usize,
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operand = match self.operand {
+            4 if self.instruction.takes_combo() => "A".to_owned(),
+            5 if self.instruction.takes_combo() => "B".to_owned(),
+            6 if self.instruction.takes_combo() => "C".to_owned(),
+            operand => operand.to_string(),
+        };
+        match self.instruction {
+            Bxc => write!(f, "{:?}", self.instruction),
+            _ => write!(f, "{:?} {operand}", self.instruction),
+        }
+    }
+}
+
+/// Returns the address of an instruction which counts opcodes and operands
+/// like jumps do
+fn address(pointer: usize) -> usize {
+    2 * pointer
+}
+
+fn describe_registers(registers: &Registers<usize, 3>) -> String {
+    format!("A={} B={} C={}", registers[A], registers[B], registers[C])
+}
+
+fn combo(registers: &Registers<usize, 3>, operand: usize) -> usize {
+    match operand {
+        o if o < 4 => o,
+        4 => registers[A],
+        5 => registers[B],
+        6 => registers[C],
+        _ => unreachable!("combo operands are checked when parsing"),
+    }
+}
+
+impl vm::Instruction for Command {
+    type Registers = Registers<usize, 3>;
+    type Output = usize;
+
+    fn execute(&self, registers: &mut Self::Registers) -> Effect<usize> {
+        let operand = self.operand;
+        let divide = |registers: &Self::Registers| {
+            let shift = u32::try_from(combo(registers, operand)).unwrap_or(u32::MAX);
+            registers[A].checked_shr(shift).unwrap_or(0)
+        };
+        match self.instruction {
+            Adv => registers[A] = divide(registers),
+            Bxl => registers[B] ^= operand,
+            Bst => registers[B] = combo(registers, operand) & 7,
+            Jnz if registers[A] == 0 => (),
+            Jnz => return Effect::Jump(operand >> 1),
+            Bxc => registers[B] ^= registers[C],
+            Out => return Effect::Output(combo(registers, operand) & 7),
+            Bdv => registers[B] = divide(registers),
+            Cdv => registers[C] = divide(registers),
+        }
+        Effect::Next
+    }
+}
+
 struct Computer {
-    a: usize,
-    b: usize,
-    c: usize,
-    program: Vec<(Instruction, usize)>,
-    pointer: usize,
+    registers: Registers<usize, 3>,
+    program: Vec<Command>,
 }
 
 impl Computer {
-    fn combo(&self, operand: usize) -> usize {
-        match operand {
-            o if o < 4 => o,
-            4 => self.a,
-            5 => self.b,
-            6 => self.c,
-            _ => panic!("invalid operand"),
+    /// Returns a machine about to run the program
+    fn machine(&self) -> Machine<Command> {
+        Machine::new(self.program.clone(), self.registers).with_step_limit(STEP_LIMIT)
+    }
+
+    /// Runs the program and returns its output
+    fn run(&self) -> Result<Vec<usize>, PuzzleError> {
+        Ok(self.machine().run()?.to_vec())
+    }
+
+    /// Runs the program with register A initially holding another number and
+    /// returns its output
+    fn run_with_a(&self, a: usize) -> Result<Vec<usize>, PuzzleError> {
+        let mut machine = self.machine();
+        machine.registers_mut()[A] = a;
+        Ok(machine.run()?.to_vec())
+    }
+
+    /// Returns the program as the list of numbers it was read from
+    fn code(&self) -> Vec<usize> {
+        (self.program.iter())
+            .flat_map(|command| [command.instruction as usize, command.operand])
+            .collect()
+    }
+
+    /// Checks that the program is a single loop running until A is zero and
+    /// shifting A by three bits on each iteration
+    fn check_loop_shape(&self) -> Result<(), PuzzleError> {
+        let unsupported = |reason: &str| Err(PuzzleError::UnsupportedInput(reason.to_owned()));
+        let (last, body) = (self.program.split_last())
+            .ok_or_else(|| PuzzleError::MissingData("instructions".to_owned()))?;
+        if last.instruction != Jnz || last.operand != 0 {
+            return unsupported("program doesn't end with a jump to its start");
+        }
+        if body.iter().any(|command| command.instruction == Jnz) {
+            return unsupported("program has more than one jump");
         }
+        let shifts = (body.iter())
+            .filter(|command| command.instruction == Adv)
+            .map(|command| command.operand)
+            .collect_vec();
+        if shifts != [3] {
+            return unsupported("program doesn't shift A by three bits exactly once");
+        }
+        Ok(())
     }
 
-    fn run(&mut self) -> String {
-        let mut output = String::new();
-        while self.pointer < self.program.len() {
-            let (instruction, operand) = self.program[self.pointer];
-            self.pointer += 1;
-            match instruction {
-                Adv => self.a /= 1 << self.combo(operand),
-                Bxl => self.b ^= operand,
-                Bst => self.b = self.combo(operand) & 7,
-                Jnz if self.a == 0 => (),
-                Jnz => self.pointer = operand >> 1,
-                Bxc => self.b ^= self.c,
-                Out if output.is_empty() => output = (self.combo(operand) & 7).to_string(),
-                Out => output.push_str(&format!(",{}", self.combo(operand) & 7)),
-                Bdv => self.b = self.a / (1 << self.combo(operand)),
-                Cdv => self.c = self.a / (1 << self.combo(operand)),
+    /// Returns the lowest initial value of A which makes the program output
+    /// the numbers of its own code
+    ///
+    /// Each iteration drops the lowest three bits of A, so the last output
+    /// only depends on the highest three bits, the second to last one on the
+    /// highest six and so on. Building A three bits at a time from the top
+    /// and keeping the values whose outputs match the end of the code finds
+    /// all solutions, and trying lower bits first finds the lowest one first.
+    fn lowest_quine_a(&self) -> Result<Option<usize>, PuzzleError> {
+        self.check_loop_shape()?;
+        let code = self.code();
+        let mut stack = vec![(0, 0)];
+        while let Some((prefix, matched)) = stack.pop() {
+            if matched == code.len() {
+                return Ok(Some(prefix));
+            }
+            for bits in (0..8).rev() {
+                let a = prefix << 3 | bits;
+                if a != 0 && self.run_with_a(a)? == code[code.len() - matched - 1..] {
+                    stack.push((a, matched + 1));
+                }
             }
         }
-        output
+        Ok(None)
     }
 }
 
-fn parse_register(line: Option<&str>, register: char) -> Result<usize, Box<dyn Error>> {
-    Ok(line
+fn parse_register(input: &str, line: Option<&str>, register: char) -> Result<usize, PuzzleError> {
+    let value = line
         .and_then(|line| line.strip_prefix(&format!("{register}: ")))
-        .ok_or(format!("register {register} not found"))?
-        .parse()?)
+        .ok_or_else(|| PuzzleError::MissingData(format!("register {register}")))?;
+    parse(input, value)
 }
 
 impl FromStr for Computer {
-    type Err = Box<dyn Error>;
+    type Err = PuzzleError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (registers, code) = s.split_once("\n\n").ok_or("blank line missing")?;
+        let (registers, code) = (s.split_once("\n\n"))
+            .ok_or_else(|| PuzzleError::MissingData("blank line after registers".to_owned()))?;
         let mut registers = (registers.lines()).flat_map(|line| line.strip_prefix("Register "));
-        let a = parse_register(registers.next(), 'A')?;
-        let b = parse_register(registers.next(), 'B')?;
-        let c = parse_register(registers.next(), 'C')?;
+        let a = parse_register(s, registers.next(), 'A')?;
+        let b = parse_register(s, registers.next(), 'B')?;
+        let c = parse_register(s, registers.next(), 'C')?;
         let mut program = Vec::new();
-        let code = (code.trim().strip_prefix("Program: ")).ok_or("program missing")?;
+        let code = (code.trim().strip_prefix("Program: "))
+            .ok_or_else(|| PuzzleError::MissingData("program".to_owned()))?;
         for mut command in &code.split(",").chunks(2) {
-            program.push((
-                command.next().unwrap().parse()?,
-                command.next().ok_or("uneven program length")?.parse()?,
-            ));
+            let opcode = command.next().unwrap();
+            let operand_str = (command.next())
+                .ok_or_else(|| PuzzleError::parse(s, opcode, "opcode without operand"))?;
+            let instruction: Instruction = parse(s, opcode)?;
+            let operand = parse(s, operand_str)?;
+            if operand >= 8 || (instruction.takes_combo() && operand == 7) {
+                Err(PuzzleError::parse(s, operand_str, "invalid operand"))?
+            }
+            program.push(Command {
+                instruction,
+                operand,
+            });
         }
         Ok(Self {
-            a,
-            b,
-            c,
+            registers: Registers([a, b, c]),
             program,
-            pointer: 0,
         })
     }
 }
 
+/// Returns the program with one instruction per line after its address and
+/// combo operands referring to registers named by them
+pub fn disassemble(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let computer: Computer = input.parse()?;
+    let disassembly = (computer.program.iter().enumerate())
+        .map(|(pointer, command)| format!("{:>3}  {command}", address(pointer)))
+        .join("\n");
+    Ok(disassembly)
+}
+
+/// Runs the program and returns the registers after each instruction followed
+/// by the output
+pub fn trace(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let computer: Computer = input.parse()?;
+    let mut machine = computer.machine().with_step_limit(TRACE_STEP_LIMIT);
+    let mut lines = vec![format!(
+        "{:12}{}",
+        "",
+        describe_registers(machine.registers())
+    )];
+    let mut pointer = machine.pointer();
+    machine.run_until(|machine| {
+        let command = machine.program()[pointer];
+        let mut line = format!(
+            "{:>3}  {:<6}  {}",
+            address(pointer),
+            command.to_string(),
+            describe_registers(machine.registers())
+        );
+        if let (Out, Some(value)) = (command.instruction, machine.output().last()) {
+            line.push_str(&format!("  out {value}"));
+        }
+        lines.push(line);
+        pointer = machine.pointer();
+        false
+    })?;
+    lines.push(format!("Output: {}", machine.output().iter().join(",")));
+    Ok(lines.join("\n"))
+}
+
+/// Computer running its program step by step and stopping at breakpoints
+///
+/// Addresses count opcodes and operands like jumps do, so instructions start
+/// at even addresses.
+pub struct Debugger {
+    machine: Machine<Command>,
+    breakpoints: BTreeSet<usize>,
+}
+
+impl Debugger {
+    /// Returns a debugger about to run the program of the input
+    pub fn new(input: &crate::PuzzleInput) -> Result<Self, PuzzleError> {
+        let computer: Computer = input.parse()?;
+        Ok(Self {
+            machine: computer.machine(),
+            breakpoints: BTreeSet::new(),
+        })
+    }
+
+    /// Adds a breakpoint stopping before the instruction at an address
+    pub fn add_breakpoint(&mut self, address: usize) {
+        self.breakpoints.insert(address);
+    }
+
+    /// Removes a breakpoint and returns whether there was one
+    pub fn remove_breakpoint(&mut self, address: usize) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    /// Returns the addresses of the breakpoints in increasing order
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> {
+        self.breakpoints.iter().copied()
+    }
+
+    /// Returns the address of the next instruction
+    pub fn address(&self) -> usize {
+        address(self.machine.pointer())
+    }
+
+    /// Returns whether the program finished
+    pub fn is_halted(&self) -> bool {
+        self.machine.is_halted()
+    }
+
+    /// Executes the next instruction
+    pub fn step(&mut self) -> Result<(), PuzzleError> {
+        self.machine.run_until(|_| true)
+    }
+
+    /// Runs until the next instruction has a breakpoint or the program
+    /// finishes
+    pub fn resume(&mut self) -> Result<(), PuzzleError> {
+        (self.machine).run_until(|machine| self.breakpoints.contains(&address(machine.pointer())))
+    }
+
+    /// Returns the next instruction, the registers and the output so far
+    pub fn state(&self) -> String {
+        let next = match self.machine.program().get(self.machine.pointer()) {
+            Some(command) => format!("{:>3}  {command}", self.address()),
+            None => "halted".to_owned(),
+        };
+        let registers = describe_registers(self.machine.registers());
+        let output = self.machine.output().iter().join(",");
+        format!("{next}\n{registers}\nOutput: {output}")
+    }
+}
+
 /// Part 1: Find the output of the program
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let mut computer: Computer = input.parse()?;
-    Ok(computer.run())
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let computer: Computer = input.parse()?;
+    Ok(computer.run()?.iter().join(","))
 }
 
+/// Part 2: Find the lowest initial value of register A making the program
+/// output itself
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let computer: Computer = input.parse()?;
+    let a = (computer.lowest_quine_a()?).ok_or_else(|| {
+        PuzzleError::NoSolution("no value of A makes the program output itself".to_owned())
+    })?;
+    Ok(a.to_string())
+}
+
+crate::register!(2024, 17, "Chronospatial Computer", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -144,9 +399,69 @@ mod tests {
 
     #[test]
     fn test_part1() {
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let disassembly = super::disassemble(&INPUT.into()).unwrap();
+        assert_eq!(disassembly, "  0  Adv 1\n  2  Out A\n  4  Jnz 0");
+        let input = INPUT.replace("0,1,5,4,3,0", "2,6,1,6,4,7,7,5");
+        let disassembly = super::disassemble(&input.into()).unwrap();
+        assert_eq!(disassembly, "  0  Bst C\n  2  Bxl 6\n  4  Bxc\n  6  Cdv B");
+    }
+
+    #[test]
+    fn test_trace() {
+        let trace = super::trace(&INPUT.into()).unwrap();
+        let lines: Vec<_> = trace.lines().collect();
+        assert_eq!(lines[0], "            A=729 B=0 C=0");
+        assert_eq!(lines[1], "  0  Adv 1   A=364 B=0 C=0");
+        assert_eq!(lines[2], "  2  Out A   A=364 B=0 C=0  out 4");
+        assert_eq!(lines[3], "  4  Jnz 0   A=364 B=0 C=0");
+        assert_eq!(lines.len(), 3 * 10 + 2);
+        assert_eq!(lines[31], "Output: 4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn test_debugger() {
+        let mut debugger = super::Debugger::new(&INPUT.into()).unwrap();
+        debugger.add_breakpoint(4);
+        debugger.resume().unwrap();
+        assert_eq!(debugger.state(), "  4  Jnz 0\nA=364 B=0 C=0\nOutput: 4");
+        debugger.step().unwrap();
+        assert_eq!(debugger.address(), 0);
+        debugger.resume().unwrap();
+        assert!(debugger.state().ends_with("Output: 4,6"));
+        assert!(debugger.remove_breakpoint(4));
+        debugger.resume().unwrap();
+        assert!(debugger.is_halted());
+        assert!(debugger.state().starts_with("halted\n"));
+    }
+
+    const QUINE: &str = concat!(
+        "Register A: 2024\n",
+        "Register B: 0\n",
+        "Register C: 0\n",
+        "\n",
+        "Program: 0,3,5,4,3,0",
+    );
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(&super::part2(&QUINE.into()).unwrap(), "117440");
+        let computer: super::Computer = QUINE.parse().unwrap();
+        assert_eq!(computer.run_with_a(117440).unwrap(), computer.code());
         assert_eq!(
-            &super::part1(INPUT.to_string()).unwrap(),
-            "4,6,3,5,6,3,5,2,1,0"
+            computer.run_with_a(117439).ok(),
+            Some(vec![7, 2, 5, 4, 3, 0])
         );
+        assert!(super::part2(&INPUT.into()).is_err());
+
+        // The common shape of actual inputs mixing the bits of A into B and C
+        let input = QUINE.replace("0,3,5,4,3,0", "2,4,1,1,7,5,4,4,1,4,0,3,5,5,3,0");
+        let computer: super::Computer = input.parse().unwrap();
+        let a = computer.lowest_quine_a().unwrap().unwrap();
+        assert_eq!(computer.run_with_a(a).unwrap(), computer.code());
     }
 }
@@ -4,23 +4,25 @@
 //!
 //! [puzzle site](https://adventofcode.com/2025/day/1)
 
-fn parse_line(line: &str) -> Result<i16, Box<dyn std::error::Error>> {
+use crate::{PuzzleError, error::parse};
+
+fn parse_line(input: &crate::PuzzleInput, line: &str) -> Result<i16, PuzzleError> {
     let mut chars = line.chars();
-    let direction = chars.next().ok_or("empty line")?;
-    let distance: i16 = chars.as_str().parse()?;
+    let direction = (chars.next()).ok_or_else(|| PuzzleError::parse(input, line, "empty line"))?;
+    let distance: i16 = parse(input, chars.as_str())?;
     match direction {
         'R' => Ok(distance),
         'L' => Ok(-distance),
-        _ => Err("invalid direction")?,
+        _ => Err(PuzzleError::parse(input, line, "invalid direction")),
     }
 }
 
 /// Part 1: Number of times 0 is encountered
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut number: i16 = 50;
     let mut count = 0;
     for line in input.lines() {
-        number = (number + parse_line(line)?).rem_euclid(100);
+        number = (number + parse_line(input, line)?).rem_euclid(100);
         if number == 0 {
             count += 1;
         }
@@ -29,12 +31,12 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 }
 
 /// Part 2: Number of times 0 is passed
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut number: i16 = 50;
     let mut count = 0;
     for line in input.lines() {
         let was_not_zero = (number != 0) as i16;
-        number += parse_line(line)?;
+        number += parse_line(input, line)?;
         count += if number.is_positive() {
             number / 100
         } else {
@@ -45,17 +47,19 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(count.to_string())
 }
 
+crate::register!(2025, 1, "Secret Entrance", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "3");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "3");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "6");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "6");
     }
 }
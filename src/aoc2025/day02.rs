@@ -5,12 +5,15 @@
 //!
 //! [puzzle site](https://adventofcode.com/2025/day/2)
 
-fn parse_input(input: String) -> Result<Vec<[u64; 2]>, Box<dyn std::error::Error>> {
+use crate::{PuzzleError, error::parse};
+
+fn parse_input(input: &crate::PuzzleInput) -> Result<Vec<[u64; 2]>, PuzzleError> {
     let mut pairs = Vec::new();
     for range in input.trim().split(',') {
-        let (start, end) = range.split_once('-').ok_or("invalid string")?;
-        let start: u64 = start.parse()?;
-        let end: u64 = end.parse()?;
+        let (start, end) = (range.split_once('-'))
+            .ok_or_else(|| PuzzleError::parse(input, range, "range without -"))?;
+        let start: u64 = parse(input, start)?;
+        let end: u64 = parse(input, end)?;
         pairs.push([start, end]);
     }
     Ok(pairs)
@@ -57,7 +60,7 @@ fn sum_ids_with_repetitions(start: u64, end: u64, repetitions: u32, only_exact:
 }
 
 /// Part 1: Invalid IDs consist of a sequence of digits repeated twice.
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut sum = 0;
     for [start, end] in parse_input(input)? {
         sum += sum_ids_with_repetitions(start, end, 2, false);
@@ -66,7 +69,7 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 }
 
 /// Part 2: Invalid IDs consist of a sequence of digits repeated at least twice.
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let mut sum = 0;
     for [start, end] in parse_input(input)? {
         for n in 2..=(end.ilog10() + 1) {
@@ -76,6 +79,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(sum.to_string())
 }
 
+crate::register!(2025, 2, "Gift Shop", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -85,11 +90,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "1227775554");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "1227775554");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "4174379265");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "4174379265");
     }
 }
@@ -6,12 +6,14 @@
 //!
 //! [puzzle site](https://adventofcode.com/2025/day/3)
 
-fn solution(input: String, digits_count: usize) -> crate::PuzzleResult {
+fn solution(input: &crate::PuzzleInput, digits_count: usize) -> crate::PuzzleResult {
     let mut sum = 0;
     for line in input.lines() {
         let mut bests = vec![0; digits_count];
-        for char in line.chars() {
-            let digit = char.to_digit(10).ok_or("invalid character")? as u64;
+        for (index, char) in line.char_indices() {
+            let digit = (char.to_digit(10))
+                .ok_or_else(|| crate::PuzzleError::parse(input, &line[index..], "not a digit"))?
+                as u64;
             let mut carry = 0;
             for best in bests.iter_mut() {
                 let candidate = carry * 10 + digit;
@@ -27,15 +29,17 @@ fn solution(input: String, digits_count: usize) -> crate::PuzzleResult {
 }
 
 /// Part 1: Two digits per line
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, 2)
 }
 
 /// Part 2: Twelve digits per line
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     solution(input, 12)
 }
 
+crate::register!(2025, 3, "Lobby", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -47,11 +51,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "357");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "357");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "3121910778619");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "3121910778619");
     }
 }
@@ -6,68 +6,69 @@
 //!
 //! [puzzle site](https://adventofcode.com/2025/day/4)
 
-fn get_accessible_rolls(map: &[Vec<char>]) -> Vec<[usize; 2]> {
-    let height = map.len();
-    let width = map[0].len();
-    let mut accessible_rolls = Vec::new();
-    for i in 0..height {
-        for j in 0..width {
-            if map[i][j] == '@' {
-                let mut neighbor_count = 0;
-                if i > 0 && j > 0 && map[i - 1][j - 1] == '@' {
-                    neighbor_count += 1;
-                }
-                if i > 0 && map[i - 1][j] == '@' {
-                    neighbor_count += 1;
-                }
-                if i > 0 && j + 1 < width && map[i - 1][j + 1] == '@' {
-                    neighbor_count += 1;
-                }
-                if j > 0 && map[i][j - 1] == '@' {
-                    neighbor_count += 1;
-                }
-                if j + 1 < width && map[i][j + 1] == '@' {
-                    neighbor_count += 1;
-                }
-                if i + 1 < height && j > 0 && map[i + 1][j - 1] == '@' {
-                    neighbor_count += 1;
-                }
-                if i + 1 < height && map[i + 1][j] == '@' {
-                    neighbor_count += 1;
-                }
-                if i + 1 < height && j + 1 < width && map[i + 1][j + 1] == '@' {
-                    neighbor_count += 1;
-                }
-                if neighbor_count < 4 {
-                    accessible_rolls.push([i, j]);
-                }
+use crate::Grid;
+use crate::visualize::{Frame, Visualize};
+
+fn get_accessible_rolls(map: &Grid<char>) -> Vec<[usize; 2]> {
+    (map.find_all(&'@'))
+        .filter(|&roll| {
+            let neighbor_count = map.neighbors8(roll).filter(|&n| map[n] == '@').count();
+            neighbor_count < 4
+        })
+        .collect()
+}
+
+/// Removes accessible rolls round by round for at most a number of rounds and
+/// returns how many were removed, emitting a frame with the accessible rolls
+/// marked (x) for each round
+fn remove_rolls(map: &mut Grid<char>, rounds: usize, visualizer: &mut dyn Visualize) -> usize {
+    let mut roll_count = 0;
+    for round in 1..=rounds {
+        let accessible_rolls = get_accessible_rolls(map);
+        if accessible_rolls.is_empty() {
+            break;
+        }
+        visualizer.frame(&|| {
+            let mut picture = map.clone();
+            for &roll in &accessible_rolls {
+                picture[roll] = 'x';
             }
+            let caption = format!("round {round}: {} accessible", accessible_rolls.len());
+            Frame::new(picture, caption)
+        });
+        roll_count += accessible_rolls.len();
+        for roll in accessible_rolls {
+            map[roll] = '.';
         }
     }
-    accessible_rolls
+    roll_count
+}
+
+/// Solves a part emitting a frame for each round of removing rolls
+pub fn visualize(
+    input: &crate::PuzzleInput,
+    part: usize,
+    visualizer: &mut dyn Visualize,
+) -> crate::PuzzleResult {
+    let mut map = input.char_grid()?;
+    let rounds = if part == 1 { 1 } else { usize::MAX };
+    Ok(remove_rolls(&mut map, rounds, visualizer).to_string())
 }
 
 /// Part 1: Number of accessible paper rolls
-pub fn part1(input: String) -> crate::PuzzleResult {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-    Ok(get_accessible_rolls(&map).len().to_string())
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    visualize(input, 1, &mut ())
 }
 
 /// Part 2: Number of removable paper rolls
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let mut map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-    let mut roll_count = 0;
-    while let accessible_rolls = get_accessible_rolls(&map)
-        && !accessible_rolls.is_empty()
-    {
-        roll_count += accessible_rolls.len();
-        for [i, j] in accessible_rolls {
-            map[i][j] = '.';
-        }
-    }
-    Ok(roll_count.to_string())
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    visualize(input, 2, &mut ())
 }
 
+crate::register!(
+    2025, 4, "Printing Department", 1 => part1, 2 => part2, visualize => visualize
+);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -85,11 +86,25 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "13");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "13");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "43");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "43");
+    }
+
+    #[test]
+    fn test_visualize() {
+        let mut recording = crate::visualize::Recording::new();
+        let answer = super::visualize(&INPUT.into(), 2, &mut recording).unwrap();
+        assert_eq!(answer, "43");
+        let frames = recording.frames();
+        assert_eq!(frames.len(), 9);
+        assert_eq!(frames[0].caption, "round 1: 13 accessible");
+        assert_eq!(
+            frames[0].picture.row(0),
+            ['.', '.', 'x', 'x', '.', 'x', 'x', '@', 'x', '.']
+        );
     }
 }
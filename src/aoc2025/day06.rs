@@ -7,33 +7,36 @@
 //!
 //! [puzzle site](https://adventofcode.com/2025/day/6)
 
-fn get_dimensions<E>(lines: &[Vec<E>]) -> Result<[usize; 2], String> {
+use crate::{PuzzleError, error::parse};
+
+fn get_dimensions<E>(lines: &[Vec<E>]) -> Result<[usize; 2], PuzzleError> {
+    let unsupported = |reason: &str| Err(PuzzleError::UnsupportedInput(reason.to_owned()));
     let height = lines.len();
     if height < 2 {
-        Err("too few lines")?
+        unsupported("too few lines")?
     }
     let width = lines[0].len();
     if !lines.iter().map(|line| line.len()).all(|len| len == width) {
-        Err("lines have different lengths")?
+        unsupported("lines have different lengths")?
     }
     Ok([width, height])
 }
 
 /// Day 1: Read numbers left to right
 #[allow(clippy::needless_range_loop)]
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let lines: Vec<Vec<&str>> =
         (input.lines().map(|line| line.split_whitespace().collect())).collect();
     let [width, height] = get_dimensions(&lines)?;
     let mut sum = 0;
     for j in 0..width {
         let operator = lines[height - 1][j];
-        let mut result: u64 = lines[0][j].parse()?;
+        let mut result: u64 = parse(input, lines[0][j])?;
         for i in 1..(height - 1) {
             result = match operator {
-                "+" => result + lines[i][j].parse::<u64>()?,
-                "*" => result * lines[i][j].parse::<u64>()?,
-                _ => Err("invalid operator")?,
+                "+" => result + parse::<u64>(input, lines[i][j])?,
+                "*" => result * parse::<u64>(input, lines[i][j])?,
+                _ => Err(PuzzleError::parse(input, operator, "invalid operator"))?,
             };
         }
         sum += result;
@@ -42,8 +45,14 @@ pub fn part1(input: String) -> crate::PuzzleResult {
 }
 
 /// Day 2: Read numbers top to bottom
-pub fn part2(input: String) -> crate::PuzzleResult {
-    let lines: Vec<Vec<char>> = (input.lines().map(|line| line.chars().collect())).collect();
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let mut lines: Vec<Vec<char>> = (input.lines().map(|line| line.chars().collect())).collect();
+    // Columns line up again once lines get back the trailing spaces trimmed
+    // from the input
+    let longest = lines.iter().map(Vec::len).max().unwrap_or(0);
+    for line in &mut lines {
+        line.resize(longest, ' ');
+    }
     let [width, height] = get_dimensions(&lines)?;
     let mut sum: u64 = 0;
     let mut start_new_problem = true;
@@ -62,13 +71,21 @@ pub fn part2(input: String) -> crate::PuzzleResult {
             }
         }
         if !number.is_empty() {
-            numbers.push(number.parse()?);
+            numbers.push(
+                number.parse().map_err(|error| {
+                    PuzzleError::UnsupportedInput(format!("{error}: {number:?}"))
+                })?,
+            );
         }
         if number.is_empty() || j == width - 1 {
             sum += match operator {
                 '+' => (numbers.iter().copied()).reduce(|result, number| result + number),
                 '*' => (numbers.iter().copied()).reduce(|result, number| result * number),
-                _ => Err("invalid operator")?,
+                _ => Err(PuzzleError::parse(
+                    input,
+                    input.lines().last().unwrap_or_default(),
+                    format!("invalid operator {operator:?}"),
+                ))?,
             }
             .unwrap_or(0);
             start_new_problem = true;
@@ -77,6 +94,8 @@ pub fn part2(input: String) -> crate::PuzzleResult {
     Ok(sum.to_string())
 }
 
+crate::register!(2025, 6, "Trash Compactor", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -88,11 +107,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "4277556");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "4277556");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "3263827");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "3263827");
     }
 }
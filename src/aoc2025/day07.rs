@@ -7,10 +7,14 @@
 //!
 //! [puzzle site](https://adventofcode.com/2025/day/7)
 
-fn solution(input: String) -> Result<[u64; 2], String> {
+fn solution(input: &crate::PuzzleInput) -> Result<[u64; 2], crate::PuzzleError> {
     let mut beam = std::collections::HashMap::new();
     let mut lines = input.lines();
-    for (index, character) in lines.next().ok_or("empty input")?.chars().enumerate() {
+    for (index, character) in (lines.next())
+        .ok_or_else(|| crate::PuzzleError::MissingData("map".to_owned()))?
+        .chars()
+        .enumerate()
+    {
         if character == 'S' {
             beam.insert(index, 1);
         }
@@ -18,14 +22,14 @@ fn solution(input: String) -> Result<[u64; 2], String> {
     let mut split_count = 0;
     for line in input.lines() {
         for (index, character) in line.chars().enumerate() {
-            if character == '^' {
-                if let Some(number) = beam.remove(&index) {
-                    split_count += 1;
-                    for new_index in [index - 1, index + 1] {
-                        beam.entry(new_index)
-                            .and_modify(|count| *count += number)
-                            .or_insert(number);
-                    }
+            if character == '^'
+                && let Some(number) = beam.remove(&index)
+            {
+                split_count += 1;
+                for new_index in [index - 1, index + 1] {
+                    beam.entry(new_index)
+                        .and_modify(|count| *count += number)
+                        .or_insert(number);
                 }
             }
         }
@@ -34,15 +38,22 @@ fn solution(input: String) -> Result<[u64; 2], String> {
 }
 
 /// Day 1: Number of splitters that can be hit
-pub fn part1(input: String) -> crate::PuzzleResult {
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     Ok(solution(input)?[0].to_string())
 }
 
 /// Day 2: Number of possible paths
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     Ok(solution(input)?[1].to_string())
 }
 
+/// Both parts from one pass through the map
+pub fn both(input: &crate::PuzzleInput) -> (crate::PuzzleResult, crate::PuzzleResult) {
+    crate::both_results(solution(input))
+}
+
+crate::register!(2025, 7, "Laboratories", 1 => part1, 2 => part2, both => both);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = concat!(
@@ -66,11 +77,17 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "21");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "21");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "40");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "40");
+    }
+
+    #[test]
+    fn test_both() {
+        let (part1, part2) = super::both(&INPUT.into());
+        assert_eq!((part1.unwrap(), part2.unwrap()), ("21".into(), "40".into()));
     }
 }
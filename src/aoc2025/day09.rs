@@ -6,13 +6,17 @@
 //!
 //! [puzzle site](https://adventofcode.com/2025/day/9)
 
-use std::error::Error;
+use crate::{PuzzleError, error::parse};
 
-fn edges_and_areas(input: String) -> Result<(Vec<[u64; 4]>, Vec<([u64; 4], u64)>), Box<dyn Error>> {
+type Rectangle = [u64; 4];
+type Areas = Vec<(Rectangle, u64)>;
+
+fn edges_and_areas(input: &crate::PuzzleInput) -> Result<(Vec<Rectangle>, Areas), PuzzleError> {
     let mut corners: Vec<[u64; 2]> = Vec::new();
     for line in input.lines() {
-        let (x, y) = line.split_once(",").ok_or("invalid line")?;
-        corners.push([x.parse()?, y.parse()?])
+        let (x, y) = (line.split_once(","))
+            .ok_or_else(|| PuzzleError::parse(input, line, "expected two coordinates"))?;
+        corners.push([parse(input, x)?, parse(input, y)?])
     }
     let mut edges = Vec::new();
     let mut areas = Vec::new();
@@ -26,18 +30,21 @@ fn edges_and_areas(input: String) -> Result<(Vec<[u64; 4]>, Vec<([u64; 4], u64)>
             ));
         }
     }
-    areas.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+    areas.sort_unstable_by_key(|area| std::cmp::Reverse(area.1));
     Ok((edges, areas))
 }
 
 /// Part 1: Largest area of any rectangle spanned by two input tiles
-pub fn part1(input: String) -> crate::PuzzleResult {
-    Ok(edges_and_areas(input)?.1[0].1.to_string())
+pub fn part1(input: &crate::PuzzleInput) -> crate::PuzzleResult {
+    let (_, areas) = edges_and_areas(input)?;
+    let (_, largest_area) =
+        (areas.first()).ok_or_else(|| PuzzleError::MissingData("two tiles".to_owned()))?;
+    Ok(largest_area.to_string())
 }
 
 /// Part 2: Largest area of any rectangle spanned by two input tiles that lies
 /// completely within the loop
-pub fn part2(input: String) -> crate::PuzzleResult {
+pub fn part2(input: &crate::PuzzleInput) -> crate::PuzzleResult {
     let (edges, areas) = edges_and_areas(input)?;
     'areas: for ([x0, y0, x1, y1], area) in areas {
         for &[x2, y2, x3, y3] in &edges {
@@ -47,20 +54,24 @@ pub fn part2(input: String) -> crate::PuzzleResult {
         }
         return Ok(area.to_string());
     }
-    unreachable!();
+    Err(PuzzleError::NoSolution(
+        "no rectangle within the loop".to_owned(),
+    ))
 }
 
+crate::register!(2025, 9, "Movie Theater", 1 => part1, 2 => part2);
+
 #[cfg(test)]
 mod tests {
     const INPUT: &str = "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3";
 
     #[test]
     fn test_part1() {
-        assert_eq!(&super::part1(INPUT.to_string()).unwrap(), "50");
+        assert_eq!(&super::part1(&INPUT.into()).unwrap(), "50");
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(&super::part2(INPUT.to_string()).unwrap(), "24");
+        assert_eq!(&super::part2(&INPUT.into()).unwrap(), "24");
     }
 }
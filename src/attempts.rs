@@ -0,0 +1,165 @@
+//! Local bookkeeping of submitted answers
+//!
+//! Every checked submission is appended to `{directory}/{year}/{day:02}.txt` as
+//! a line `{part} {verdict} {answer}` with the verdict being one of `correct`,
+//! `wrong`, `too-high`, or `too-low`. Submissions the website refused to check
+//! aren't recorded.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::client::Verdict;
+
+/// One checked submission
+#[derive(Debug, PartialEq)]
+pub struct Attempt {
+    /// Part of the puzzle (1 or 2)
+    pub part: usize,
+    /// Submitted answer
+    pub answer: String,
+    /// Verdict of the website
+    pub verdict: Verdict,
+}
+
+/// All checked submissions for a certain puzzle
+pub struct Attempts {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+fn verdict_from_keyword(keyword: &str) -> Option<Verdict> {
+    Some(match keyword {
+        "correct" => Verdict::Correct,
+        "wrong" => Verdict::Wrong,
+        "too-high" => Verdict::TooHigh,
+        "too-low" => Verdict::TooLow,
+        _ => None?,
+    })
+}
+
+fn keyword_from_verdict(verdict: Verdict) -> Option<&'static str> {
+    Some(match verdict {
+        Verdict::Correct => "correct",
+        Verdict::Wrong => "wrong",
+        Verdict::TooHigh => "too-high",
+        Verdict::TooLow => "too-low",
+        Verdict::Wait { .. } | Verdict::WrongLevel => None?,
+    })
+}
+
+impl Attempts {
+    /// Reads the attempts for a puzzle from a directory (no file means no attempts)
+    pub fn load(directory: &str, year: usize, day: usize) -> Result<Self, String> {
+        let path = PathBuf::from(format!("{directory}/{year}/{day:02}.txt"));
+        let mut attempts = Vec::new();
+        if path.exists() {
+            let content = std::fs::read_to_string(&path)
+                .or(Err(format!("couldn't read {}", path.display())))?;
+            for line in content.lines().filter(|line| !line.is_empty()) {
+                let invalid = || format!("invalid attempt in {}: {line}", path.display());
+                let mut fields = line.splitn(3, ' ');
+                let part = fields.next().and_then(|part| part.parse().ok());
+                let verdict = fields.next().and_then(verdict_from_keyword);
+                let answer = fields.next();
+                let (Some(part), Some(verdict), Some(answer)) = (part, verdict, answer) else {
+                    return Err(invalid());
+                };
+                attempts.push(Attempt {
+                    part,
+                    answer: answer.to_owned(),
+                    verdict,
+                });
+            }
+        }
+        Ok(Self { path, attempts })
+    }
+
+    /// Returns the attempts made so far in the order they were made
+    pub fn iter(&self) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter()
+    }
+
+    /// Returns an error explaining why an answer shouldn't be submitted if
+    /// previous attempts already tell that it's wrong
+    pub fn check(&self, part: usize, answer: &str) -> Result<(), String> {
+        let number = answer.parse::<i128>().ok();
+        for attempt in self.iter().filter(|attempt| attempt.part == part) {
+            let previous = &attempt.answer;
+            let bound = attempt.answer.parse::<i128>().ok();
+            match attempt.verdict {
+                Verdict::Correct if previous == answer => {
+                    Err(format!("{answer} is already known to be correct"))?
+                }
+                Verdict::Correct => Err(format!("{previous} is already known to be correct"))?,
+                _ if previous == answer => Err(format!(
+                    "{answer} is already known to be {}",
+                    attempt.verdict
+                ))?,
+                Verdict::TooHigh if number.zip(bound).is_some_and(|(n, b)| n > b) => Err(format!(
+                    "{answer} is higher than {previous} which is too high"
+                ))?,
+                Verdict::TooLow if number.zip(bound).is_some_and(|(n, b)| n < b) => Err(format!(
+                    "{answer} is lower than {previous} which is too low"
+                ))?,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a submission to the attempts and appends it to the file, unless the
+    /// website refused to check it
+    pub fn record(&mut self, part: usize, answer: &str, verdict: Verdict) -> Result<(), String> {
+        let Some(keyword) = keyword_from_verdict(verdict) else {
+            return Ok(());
+        };
+        let error = || format!("couldn't record attempt in {}", self.path.display());
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).or(Err(error()))?;
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{part} {keyword} {answer}"))
+            .or(Err(error()))?;
+        self.attempts.push(Attempt {
+            part,
+            answer: answer.to_owned(),
+            verdict,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Attempts;
+    use crate::client::Verdict;
+
+    #[test]
+    fn test_attempts() {
+        let directory = std::env::temp_dir().join(format!("attempts-{}", std::process::id()));
+        let directory = directory.to_str().unwrap();
+        let mut attempts = Attempts::load(directory, 2023, 5).unwrap();
+        attempts.record(1, "100", Verdict::TooHigh).unwrap();
+        attempts.record(1, "20", Verdict::TooLow).unwrap();
+        attempts.record(1, "50", Verdict::Wrong).unwrap();
+        attempts
+            .record(1, "60", Verdict::Wait { seconds: 30 })
+            .unwrap();
+        attempts.record(2, "abc", Verdict::Correct).unwrap();
+
+        let attempts = Attempts::load(directory, 2023, 5).unwrap();
+        std::fs::remove_dir_all(directory).unwrap();
+        assert_eq!(attempts.iter().count(), 4);
+        for answer in ["100", "101", "20", "19", "50"] {
+            assert!(attempts.check(1, answer).is_err(), "{answer} not refused");
+        }
+        for answer in ["99", "21", "60", "abc"] {
+            assert!(attempts.check(1, answer).is_ok(), "{answer} refused");
+        }
+        assert!(attempts.check(2, "abc").is_err());
+        assert!(attempts.check(2, "xyz").is_err());
+    }
+}
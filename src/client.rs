@@ -0,0 +1,239 @@
+//! Talking to the Advent of Code website
+
+use std::fmt::{Display, Formatter};
+
+use regex::Regex;
+use reqwest::blocking::Client;
+
+/// Where the Advent of Code website is found unless configured otherwise
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Logged-in session on the Advent of Code website
+pub struct Session {
+    /// Value of the session cookie
+    pub token: String,
+    /// URL of the website without trailing slash
+    pub base_url: String,
+}
+
+impl Session {
+    /// Returns a session on the default website for a session token
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.trim().to_owned(),
+            base_url: DEFAULT_BASE_URL.to_owned(),
+        }
+    }
+
+    /// Returns the session using another website like a local test server
+    pub fn with_base_url(self, base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            ..self
+        }
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.token)
+    }
+}
+
+/// How the website responded to a submitted answer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    /// The answer is right
+    Correct,
+    /// The answer is wrong without further hints
+    Wrong,
+    /// The answer is wrong and too high
+    TooHigh,
+    /// The answer is wrong and too low
+    TooLow,
+    /// The answer wasn't checked because the last one was submitted too recently
+    Wait {
+        /// Seconds left to wait before submitting again
+        seconds: u64,
+    },
+    /// The answer wasn't checked because the part is locked or already solved
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Returns the verdict found in the HTML response to a submission
+    pub fn from_response(response: &str) -> Option<Self> {
+        Some(if response.contains("That's the right answer") {
+            Self::Correct
+        } else if response.contains("That's not the right answer") {
+            if response.contains("your answer is too high") {
+                Self::TooHigh
+            } else if response.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            }
+        } else if response.contains("You gave an answer too recently") {
+            let re = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+            let seconds = re.captures(response).map_or(0, |caps| {
+                let number = |index| {
+                    caps.get(index)
+                        .map_or(0, |m| m.as_str().parse().unwrap_or(0))
+                };
+                60 * number(1) + number(2)
+            });
+            Self::Wait { seconds }
+        } else if response.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            None?
+        })
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+            Self::Wait { seconds } => write!(f, "wait {seconds}s before submitting again"),
+            Self::WrongLevel => write!(f, "part is locked or already solved"),
+        }
+    }
+}
+
+/// Returns the input for a certain Advent of Code puzzle or an error if the download fails
+pub fn download_input(session: &Session, year: usize, day: usize) -> Result<String, String> {
+    let url = format!("{}/{year}/day/{day}/input", session.base_url);
+    Client::new()
+        .get(&url)
+        .header("Cookie", session.cookie())
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .or(Err(format!("couldn't download from {url}")))
+}
+
+/// Submits an answer for a certain puzzle part and returns the website's verdict
+pub fn submit_answer(
+    session: &Session,
+    year: usize,
+    day: usize,
+    part: usize,
+    answer: &str,
+) -> Result<Verdict, String> {
+    let url = format!("{}/{year}/day/{day}/answer", session.base_url);
+    let response = Client::new()
+        .post(&url)
+        .header("Cookie", session.cookie())
+        .form(&[("level", part.to_string().as_str()), ("answer", answer)])
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .or(Err(format!("couldn't submit to {url}")))?;
+    Verdict::from_response(&response).ok_or(format!("unexpected response from {url}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Session, Verdict};
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    /// Serves one response on a local port and returns the base URL and the
+    /// request received
+    fn stub_server(body: &'static str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let responses = [
+            (
+                "That's the right answer!  You are one gold star closer.",
+                Verdict::Correct,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, ...",
+                Verdict::Wrong,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Verdict::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::TooLow,
+            ),
+            (
+                "You gave an answer too recently; ...  You have 1m 5s left to wait.",
+                Verdict::Wait { seconds: 65 },
+            ),
+            (
+                "You gave an answer too recently; ...  You have 37s left to wait.",
+                Verdict::Wait { seconds: 37 },
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Verdict::WrongLevel,
+            ),
+        ];
+        for (response, verdict) in responses {
+            let html = format!("<main><article><p>{response}</p></article></main>");
+            assert_eq!(Verdict::from_response(&html), Some(verdict));
+        }
+        assert_eq!(Verdict::from_response("<html></html>"), None);
+    }
+
+    #[test]
+    fn test_submit_answer() {
+        let (base_url, handle) =
+            stub_server("<article><p>That's not the right answer; your answer is too low.</p>");
+        let session = Session::new("secret\n").with_base_url(&base_url);
+        let verdict = super::submit_answer(&session, 2024, 7, 2, "1234").unwrap();
+        assert_eq!(verdict, Verdict::TooLow);
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/7/answer HTTP/1.1\r\n"));
+        assert!(request.contains("cookie: session=secret\r\n"));
+        assert!(request.ends_with("level=2&answer=1234"));
+    }
+
+    #[test]
+    fn test_download_input() {
+        let (base_url, handle) = stub_server("1\n2\n3\n");
+        let session = Session::new("secret").with_base_url(&format!("{base_url}/"));
+        assert_eq!(
+            super::download_input(&session, 2022, 1).unwrap(),
+            "1\n2\n3\n"
+        );
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+    }
+}
@@ -0,0 +1,221 @@
+//! Clustering of elements and points
+//!
+//! Elements are numbered from 0 and points are given as a slice whose indices
+//! number them.
+
+use std::collections::BinaryHeap;
+
+/// Partition of elements into disjoint components which can be merged
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// Returns `len` elements each in a component of its own
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            components: len,
+        }
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns whether there are no elements
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns the number of components
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// Returns the representative element of an element's component
+    pub fn find(&mut self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            let grandparent = self.parents[self.parents[element]];
+            self.parents[element] = grandparent;
+            element = grandparent;
+        }
+        element
+    }
+
+    /// Merges the components of two elements and returns whether they were
+    /// different components
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.ranks[a] < self.ranks[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        if self.ranks[a] == self.ranks[b] {
+            self.ranks[a] += 1;
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.components -= 1;
+        true
+    }
+
+    /// Returns whether two elements are in the same component
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Returns the number of elements in an element's component
+    pub fn size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Returns the sizes of all components
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&element| self.parents[element] == element)
+            .map(|root| self.sizes[root])
+            .collect()
+    }
+}
+
+/// Returns the squared Euclidean distance between two points
+pub fn squared_distance(a: [i64; 3], b: [i64; 3]) -> u64 {
+    (a.iter().zip(b)).map(|(a, b)| a.abs_diff(b).pow(2)).sum()
+}
+
+/// Returns the `k` pairs of points closest to each other with their squared
+/// distances in ascending order
+///
+/// Pairs at equal distances are ordered by their indices. All pairs are
+/// considered but only `k` are kept at a time, so memory doesn't grow with
+/// the square of the number of points.
+pub fn closest_pairs(points: &[[i64; 3]], k: usize) -> Vec<([usize; 2], u64)> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut heap = BinaryHeap::with_capacity(k);
+    for (i, &a) in points.iter().enumerate() {
+        for (j, &b) in points.iter().enumerate().skip(i + 1) {
+            let pair = (squared_distance(a, b), [i, j]);
+            if heap.len() < k {
+                heap.push(pair);
+            } else if heap.peek().is_some_and(|furthest| pair < *furthest) {
+                heap.pop();
+                heap.push(pair);
+            }
+        }
+    }
+    (heap.into_sorted_vec().into_iter())
+        .map(|(distance, pair)| (pair, distance))
+        .collect()
+}
+
+/// Returns the pairs of points connecting all points with the least total
+/// distance with their squared distances in ascending order
+///
+/// Pairs at equal distances are ordered by their indices, which makes the
+/// pairs unique. Each point is joined to the tree through its closest
+/// connection (Prim's algorithm), so every pair of points is looked at once
+/// and memory grows only with the number of points.
+pub fn minimum_spanning_tree(points: &[[i64; 3]]) -> Vec<([usize; 2], u64)> {
+    // Closest connection of each point not in the tree yet
+    let mut closest = vec![(u64::MAX, [usize::MAX; 2]); points.len()];
+    let mut outside: Vec<usize> = (1..points.len()).collect();
+    let mut pairs = Vec::with_capacity(outside.len());
+    let mut newest = 0;
+    while !outside.is_empty() {
+        // Update connections to the newest point while finding the closest
+        let mut nearest = 0;
+        for (position, &j) in outside.iter().enumerate() {
+            let pair = (
+                squared_distance(points[newest], points[j]),
+                [newest.min(j), newest.max(j)],
+            );
+            closest[j] = closest[j].min(pair);
+            if closest[j] < closest[outside[nearest]] {
+                nearest = position;
+            }
+        }
+        let (distance, pair) = closest[outside[nearest]];
+        newest = outside.swap_remove(nearest);
+        pairs.push((pair, distance));
+    }
+    pairs.sort_unstable_by_key(|&(pair, distance)| (distance, pair));
+    pairs
+}
+
+/// Connects pairs of points from closest to furthest until all points form
+/// one cluster and returns the pair making the last connection
+///
+/// Only pairs of the minimum spanning tree are looked at since the others
+/// connect points already in the same cluster. There is no last connection
+/// if there are fewer than two points.
+pub fn connect_all(points: &[[i64; 3]]) -> Option<[usize; 2]> {
+    let mut clusters = DisjointSet::new(points.len());
+    for ([i, j], _) in minimum_spanning_tree(points) {
+        if clusters.union(i, j) && clusters.components() == 1 {
+            return Some([i, j]);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DisjointSet, closest_pairs, connect_all, minimum_spanning_tree};
+
+    #[test]
+    fn test_disjoint_set() {
+        let mut set = DisjointSet::new(6);
+        assert!(set.union(0, 1) && set.union(2, 3) && set.union(1, 3));
+        assert!(!set.union(0, 2));
+        assert!(set.same(0, 3) && !set.same(0, 4));
+        assert_eq!((set.size(2), set.size(5), set.components()), (4, 1, 3));
+        let mut sizes = set.component_sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 1, 4]);
+    }
+
+    #[test]
+    fn test_closest_pairs() {
+        let points = [[0, 0, 0], [10, 0, 0], [0, 3, 4], [10, 1, 0]];
+        let pairs = closest_pairs(&points, 3);
+        assert_eq!(pairs, [([1, 3], 1), ([0, 2], 25), ([0, 1], 100)]);
+        assert_eq!(closest_pairs(&points, 10).len(), 6);
+        assert_eq!(connect_all(&points), Some([0, 1]));
+        assert_eq!(connect_all(&points[..1]), None);
+    }
+
+    #[test]
+    fn test_minimum_spanning_tree() {
+        let points = [[0, 0, 0], [10, 0, 0], [0, 3, 4], [10, 1, 0], [5, 0, 0]];
+        let tree = minimum_spanning_tree(&points);
+        assert_eq!(
+            tree,
+            [([1, 3], 1), ([0, 2], 25), ([0, 4], 25), ([1, 4], 25)]
+        );
+        assert!(minimum_spanning_tree(&points[..1]).is_empty());
+
+        // Connecting closest pairs first merges clusters along the same pairs
+        let points: Vec<[i64; 3]> = (0..40)
+            .map(|k: i64| [(k * 37) % 23, (k * 11) % 7, (k * k) % 13])
+            .collect();
+        let mut clusters = DisjointSet::new(points.len());
+        let merges: Vec<_> = (closest_pairs(&points, 40 * 39 / 2).into_iter())
+            .filter(|&([i, j], _)| clusters.union(i, j))
+            .collect();
+        assert_eq!(minimum_spanning_tree(&points), merges);
+        assert_eq!(connect_all(&points), merges.last().map(|&(pair, _)| pair));
+    }
+}
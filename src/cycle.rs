@@ -0,0 +1,175 @@
+//! Detection of repetitions in sequences of states
+//!
+//! A sequence starts with an initial state and each state determines the next
+//! one through a step function, so once a state repeats all following states
+//! repeat as well. Steps are counted from 0 for the initial state. Detection
+//! doesn't terminate for sequences which never repeat.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Shape of a sequence of states which repeats after a prefix
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Cycle {
+    /// Number of steps before the first state which repeats
+    pub prefix_len: usize,
+    /// Number of steps between repetitions of a state
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Returns the earliest step whose state equals the state at step `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix_len {
+            n
+        } else {
+            self.prefix_len + (n - self.prefix_len) % self.period
+        }
+    }
+
+    /// Returns the state at step `n` by taking only the steps before the
+    /// first repetition
+    pub fn nth<S>(&self, initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+        let mut state = initial;
+        for _ in 0..self.reduce(n) {
+            state = step(&state);
+        }
+        state
+    }
+}
+
+/// Finds the repetition by remembering a fingerprint of every state until one
+/// is seen again
+///
+/// States with equal fingerprints are taken to be equal, so the fingerprint
+/// needs to identify a state but can be smaller than the state itself.
+pub fn find_by_key<S, K: Hash + Eq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    let mut n = 0;
+    loop {
+        if let Some(previous) = seen.insert(key(&state), n) {
+            return Cycle {
+                prefix_len: previous,
+                period: n - previous,
+            };
+        }
+        state = step(&state);
+        n += 1;
+    }
+}
+
+/// Finds the repetition by remembering every state until one is seen again
+pub fn find<S: Clone + Hash + Eq>(initial: S, step: impl FnMut(&S) -> S) -> Cycle {
+    find_by_key(initial, step, S::clone)
+}
+
+/// Returns the state at step `n` skipping repetitions found by remembering
+/// every state
+pub fn nth<S: Clone + Hash + Eq>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    find(initial.clone(), &mut step).nth(initial, step, n)
+}
+
+/// Finds the repetition with Floyd's tortoise and hare keeping only two
+/// states at a time
+pub fn floyd<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // The hare moves twice as fast until both meet within the cycle
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    // Their distance is a multiple of the period now, so moving the tortoise
+    // back to the start and both at the same speed lets them meet at the
+    // first repeating state
+    let mut prefix_len = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix_len += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+    Cycle { prefix_len, period }
+}
+
+/// Finds the repetition with Brent's algorithm keeping only two states at a
+/// time and usually taking fewer steps than [`floyd`]
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // Search for the period with the tortoise teleporting to the hare at
+    // powers of two
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // Let the hare lead by the period and move both until they meet
+    let mut prefix_len = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix_len += 1;
+    }
+    Cycle { prefix_len, period }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cycle, brent, find, find_by_key, floyd, nth};
+
+    /// Steps 0, 1, 2 followed by 3, 4, 5, 6 repeating
+    fn step(state: &u32) -> u32 {
+        if *state < 6 { state + 1 } else { 3 }
+    }
+
+    #[test]
+    fn test_cycle() {
+        let expected = Cycle {
+            prefix_len: 3,
+            period: 4,
+        };
+        assert_eq!(find(0, step), expected);
+        assert_eq!(find_by_key(0, step, |&state| state * 2), expected);
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        let purely_periodic = Cycle {
+            prefix_len: 0,
+            period: 4,
+        };
+        assert_eq!(find(4, step), purely_periodic);
+        assert_eq!(floyd(5, step), purely_periodic);
+        assert_eq!(brent(6, step), purely_periodic);
+
+        assert_eq!((expected.reduce(2), expected.reduce(7)), (2, 3));
+        assert_eq!(expected.nth(0, step, 1_000_000_001), 5);
+        assert_eq!(nth(0, step, 1_000_000_000), 4);
+        assert_eq!(nth(0, |&state: &u32| (state + 1).min(2), 5), 2);
+    }
+}
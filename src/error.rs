@@ -0,0 +1,114 @@
+//! Error type shared by all puzzle solutions
+
+use std::fmt::{Display, Formatter};
+
+/// Error returned by puzzle solutions
+#[derive(Clone, Debug, PartialEq)]
+pub enum PuzzleError {
+    /// Part of the input couldn't be parsed
+    Parse {
+        /// One-based line number in the input
+        line: usize,
+        /// One-based column number (in characters) in the input
+        column: usize,
+        /// What went wrong
+        message: String,
+        /// The whole input line the error was found on
+        excerpt: String,
+    },
+    /// Something the solution relies on is missing in the input
+    MissingData(String),
+    /// The input has a shape the solution can't handle
+    UnsupportedInput(String),
+    /// The input doesn't lead to any solution
+    NoSolution(String),
+    /// There is no registered solution for this puzzle
+    NotImplemented {
+        /// Year of the puzzle
+        year: usize,
+        /// Day of the puzzle
+        day: usize,
+        /// Part of the puzzle
+        part: usize,
+    },
+}
+
+impl PuzzleError {
+    /// Returns a parse error located at a fragment of the input
+    ///
+    /// The fragment should be a slice of the input, otherwise it is located by
+    /// its first occurrence in the input or at the end of the input.
+    pub fn parse(input: &str, fragment: &str, message: impl Display) -> Self {
+        let input_start = input.as_ptr() as usize;
+        let fragment_start = fragment.as_ptr() as usize;
+        let offset = if (input_start..=input_start + input.len()).contains(&fragment_start) {
+            fragment_start - input_start
+        } else {
+            input.find(fragment).unwrap_or(input.len())
+        };
+        let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |index| offset + index);
+        Self::Parse {
+            line: 1 + input[..offset].matches('\n').count(),
+            column: 1 + input[line_start..offset].chars().count(),
+            message: message.to_string(),
+            excerpt: input[line_start..line_end].to_owned(),
+        }
+    }
+}
+
+/// Parses a fragment of the input returning a located error on failure
+pub fn parse<T>(input: &str, fragment: &str) -> Result<T, PuzzleError>
+where
+    T: std::str::FromStr,
+    T::Err: Display,
+{
+    fragment
+        .parse()
+        .map_err(|error| PuzzleError::parse(input, fragment, format!("{error}: {fragment:?}")))
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse {
+                line,
+                column,
+                message,
+                excerpt,
+            } => {
+                let margin = " ".repeat(line.to_string().len());
+                writeln!(f, "line {line}, column {column}: {message}")?;
+                writeln!(f, "{margin} |\n{line} | {excerpt}")?;
+                write!(f, "{margin} | {}^", " ".repeat(column - 1))
+            }
+            Self::MissingData(what) => write!(f, "missing {what}"),
+            Self::UnsupportedInput(reason) => write!(f, "unsupported input: {reason}"),
+            Self::NoSolution(reason) => write!(f, "no solution found: {reason}"),
+            Self::NotImplemented { year, day, part } => {
+                write!(f, "no solution for day {day} part {part} of {year}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+#[cfg(test)]
+mod tests {
+    use super::PuzzleError;
+
+    #[test]
+    fn test_parse() {
+        let input = "12 34\n56 x8\n90";
+        let fragment = input.lines().nth(1).unwrap().split(' ').nth(1).unwrap();
+        let error = super::parse::<u8>(input, fragment).unwrap_err();
+        let &PuzzleError::Parse { line, column, .. } = &error else {
+            panic!("not a parse error")
+        };
+        assert_eq!((line, column), (2, 4));
+        assert!(error.to_string().ends_with("2 | 56 x8\n  |    ^"));
+    }
+}
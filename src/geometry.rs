@@ -0,0 +1,400 @@
+//! Directions, points and vectors on a square lattice
+//!
+//! Coordinates follow the screen convention of grids: x grows to the right and
+//! y grows downwards, so up is the negative y direction. Grid positions
+//! `[row, column]` correspond to points with `x = column` and `y = row`.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use num::Signed;
+
+/// One of the four orthogonal directions in clockwise order starting up
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Dir4 {
+    /// Negative y, north
+    Up,
+    /// Positive x, east
+    Right,
+    /// Positive y, south
+    Down,
+    /// Negative x, west
+    Left,
+}
+
+impl Dir4 {
+    /// All directions in clockwise order starting up
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Returns the direction's position in [`Dir4::ALL`] for indexing arrays
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the direction after turning 90° counterclockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 3) % 4]
+    }
+
+    /// Returns the direction after turning 90° clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 4]
+    }
+
+    /// Returns the opposite direction
+    pub fn turn_around(self) -> Self {
+        Self::ALL[(self.index() + 2) % 4]
+    }
+
+    /// Returns whether the direction is left or right
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+
+    /// Returns the unit step as `[rows, columns]` for grid positions
+    pub fn offset(self) -> [isize; 2] {
+        match self {
+            Self::Up => [-1, 0],
+            Self::Right => [0, 1],
+            Self::Down => [1, 0],
+            Self::Left => [0, -1],
+        }
+    }
+
+    /// Returns the direction of the step between adjacent grid positions
+    pub fn between(from: [usize; 2], to: [usize; 2]) -> Option<Self> {
+        Self::ALL.into_iter().find(|direction| {
+            let [di, dj] = direction.offset();
+            [
+                from[0].checked_add_signed(di),
+                from[1].checked_add_signed(dj),
+            ] == [Some(to[0]), Some(to[1])]
+        })
+    }
+
+    /// Returns the unit vector
+    pub fn vector<T: Signed>(self) -> Vector<T> {
+        let [y, x] = self.offset().map(|delta| match delta {
+            -1 => -T::one(),
+            1 => T::one(),
+            _ => T::zero(),
+        });
+        Vector { x, y }
+    }
+
+    /// Returns the direction of an arrow `^`, `>`, `v` or `<`
+    pub fn from_arrow(character: char) -> Option<Self> {
+        Some(match character {
+            '^' => Self::Up,
+            '>' => Self::Right,
+            'v' => Self::Down,
+            '<' => Self::Left,
+            _ => None?,
+        })
+    }
+
+    /// Returns the direction of an initial `U`, `R`, `D` or `L`
+    pub fn from_letter(character: char) -> Option<Self> {
+        Some(match character {
+            'U' => Self::Up,
+            'R' => Self::Right,
+            'D' => Self::Down,
+            'L' => Self::Left,
+            _ => None?,
+        })
+    }
+
+    /// Returns the direction of a compass point `N`, `E`, `S` or `W`
+    pub fn from_compass(character: char) -> Option<Self> {
+        Some(match character {
+            'N' => Self::Up,
+            'E' => Self::Right,
+            'S' => Self::Down,
+            'W' => Self::Left,
+            _ => None?,
+        })
+    }
+
+    /// Returns the arrow `^`, `>`, `v` or `<` pointing in the direction
+    pub fn to_arrow(self) -> char {
+        ['^', '>', 'v', '<'][self.index()]
+    }
+}
+
+/// One of the eight orthogonal or diagonal directions in clockwise order
+/// starting up
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Dir8 {
+    /// Negative y, north
+    Up,
+    /// Positive x and negative y, northeast
+    UpRight,
+    /// Positive x, east
+    Right,
+    /// Positive x and positive y, southeast
+    DownRight,
+    /// Positive y, south
+    Down,
+    /// Negative x and positive y, southwest
+    DownLeft,
+    /// Negative x, west
+    Left,
+    /// Negative x and negative y, northwest
+    UpLeft,
+}
+
+impl Dir8 {
+    /// All directions in clockwise order starting up
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Returns the direction's position in [`Dir8::ALL`] for indexing arrays
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the direction after turning 45° counterclockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Returns the direction after turning 45° clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    /// Returns the opposite direction
+    pub fn turn_around(self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    /// Returns whether the direction is diagonal
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// Returns the step as `[rows, columns]` for grid positions
+    pub fn offset(self) -> [isize; 2] {
+        match self {
+            Self::Up => [-1, 0],
+            Self::UpRight => [-1, 1],
+            Self::Right => [0, 1],
+            Self::DownRight => [1, 1],
+            Self::Down => [1, 0],
+            Self::DownLeft => [1, -1],
+            Self::Left => [0, -1],
+            Self::UpLeft => [-1, -1],
+        }
+    }
+
+    /// Returns the step vector, whose components are -1, 0 or 1
+    pub fn vector<T: Signed>(self) -> Vector<T> {
+        let [y, x] = self.offset().map(|delta| match delta {
+            -1 => -T::one(),
+            1 => T::one(),
+            _ => T::zero(),
+        });
+        Vector { x, y }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(direction: Dir4) -> Self {
+        Self::ALL[2 * direction.index()]
+    }
+}
+
+/// Displacement between two points
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Vector<T> {
+    /// Horizontal component growing to the right
+    pub x: T,
+    /// Vertical component growing downwards
+    pub y: T,
+}
+
+impl<T> Vector<T> {
+    /// Returns the vector with the given components
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Signed + Copy> Vector<T> {
+    /// Returns the vector rotated by 90° counterclockwise
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Returns the vector rotated by 90° clockwise
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Returns the sum of the absolute components
+    pub fn manhattan(self) -> T {
+        self.x.abs() + self.y.abs()
+    }
+
+    /// Returns the larger absolute component, which is the number of king
+    /// moves on a chessboard
+    pub fn chebyshev(self) -> T
+    where
+        T: PartialOrd,
+    {
+        let (x, y) = (self.x.abs(), self.y.abs());
+        if x < y { y } else { x }
+    }
+
+    /// Returns the vector with each component replaced by its sign
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+}
+
+/// Location on the lattice
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point<T> {
+    /// Horizontal coordinate growing to the right
+    pub x: T,
+    /// Vertical coordinate growing downwards
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    /// Returns the point with the given coordinates
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Signed + Copy> Point<T> {
+    /// Returns the Manhattan distance to another point
+    pub fn manhattan_distance(self, other: Self) -> T {
+        (other - self).manhattan()
+    }
+}
+
+impl<T: TryInto<usize>> Point<T> {
+    /// Returns the grid position `[row, column]` of the point unless it has a
+    /// negative coordinate
+    pub fn position(self) -> Option<[usize; 2]> {
+        Some([self.y.try_into().ok()?, self.x.try_into().ok()?])
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vector<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vector<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vector<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn add(self, vector: Vector<T>) -> Self {
+        Self::new(self.x + vector.x, self.y + vector.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn sub(self, vector: Vector<T>) -> Self {
+        Self::new(self.x - vector.x, self.y - vector.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, other: Self) -> Vector<T> {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign<Vector<T>> for Point<T> {
+    fn add_assign(&mut self, vector: Vector<T>) {
+        self.x += vector.x;
+        self.y += vector.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign<Vector<T>> for Point<T> {
+    fn sub_assign(&mut self, vector: Vector<T>) {
+        self.x -= vector.x;
+        self.y -= vector.y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dir4, Dir8, Point, Vector};
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.turn_right(), Dir4::Up);
+        assert_eq!(Dir4::Right.turn_around(), Dir4::Left);
+        assert_eq!(Dir4::from_arrow('v'), Dir4::from_compass('S'));
+        assert_eq!(Dir4::from_letter('R'), Some(Dir4::Right));
+        assert_eq!(Dir4::from_letter('X'), None);
+        assert_eq!(Dir4::Down.vector::<i32>(), Vector::new(0, 1));
+        assert_eq!(Dir4::between([1, 1], [1, 0]), Some(Dir4::Left));
+        assert_eq!(Dir4::between([0, 0], [1, 1]), None);
+        assert_eq!(Dir8::from(Dir4::Left).turn_right(), Dir8::UpLeft);
+        assert_eq!(Dir8::UpLeft.turn_right(), Dir8::Up);
+        assert_eq!(Dir8::DownLeft.vector::<i8>(), Vector::new(-1, 1));
+    }
+
+    #[test]
+    fn test_points() {
+        let point = Point::new(2, -1) + Dir4::Right.vector() * 3;
+        assert_eq!(point, Point::new(5, -1));
+        assert_eq!(point.manhattan_distance(Point::new(0, 0)), 6);
+        assert_eq!(Vector::new(3, 1).rotate_right(), Vector::new(-1, 3));
+        assert_eq!(Vector::new(3, 1).rotate_left(), Vector::new(1, -3));
+        assert_eq!(Point::new(3, 4).position(), Some([4, 3]));
+        assert_eq!(Point::new(3, -4).position(), None);
+    }
+}
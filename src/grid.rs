@@ -0,0 +1,350 @@
+//! Rectangular grids of tiles
+//!
+//! Tiles are addressed by positions `[row, column]` with row 0 at the top and
+//! column 0 at the left.
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::PuzzleError;
+use crate::geometry::Dir4;
+
+/// Tile which can be read from and rendered as a character
+pub trait Tile: Sized {
+    /// Returns the tile shown by a character if there is one
+    fn from_char(character: char) -> Option<Self>;
+
+    /// Returns the character showing the tile
+    fn to_char(&self) -> char;
+}
+
+impl Tile for char {
+    fn from_char(character: char) -> Option<Self> {
+        Some(character)
+    }
+
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+/// Decimal digits
+impl Tile for u8 {
+    fn from_char(character: char) -> Option<Self> {
+        character.to_digit(10).map(|digit| digit as u8)
+    }
+
+    fn to_char(&self) -> char {
+        char::from_digit(*self as u32, 10).unwrap_or('?')
+    }
+}
+
+/// Rectangular grid with tiles stored row by row
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    tiles: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Returns a grid filled with copies of one tile
+    pub fn new(width: usize, height: usize, tile: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            tiles: vec![tile; width * height],
+        }
+    }
+
+    /// Returns the grid made of rows unless they differ in length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        if rows.iter().any(|row| row.len() != width) {
+            None?
+        }
+        let tiles = rows.into_iter().flatten().collect();
+        Some(Self {
+            width,
+            height,
+            tiles,
+        })
+    }
+
+    /// Reads a grid from lines of characters converting each character at its
+    /// position into a tile
+    ///
+    /// Errors point at the offending character or line within `input`.
+    pub fn parse_with(
+        input: &str,
+        mut convert: impl FnMut([usize; 2], char) -> Option<T>,
+    ) -> Result<Self, PuzzleError> {
+        let mut tiles = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (i, line) in input.lines().enumerate() {
+            let mut length = 0;
+            for (j, (index, character)) in line.char_indices().enumerate() {
+                let error = || PuzzleError::parse(input, &line[index..], "unexpected character");
+                tiles.push(convert([i, j], character).ok_or_else(error)?);
+                length += 1;
+            }
+            if *width.get_or_insert(length) != length {
+                Err(PuzzleError::parse(
+                    input,
+                    line,
+                    "line length differs from first line",
+                ))?
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            tiles,
+        })
+    }
+
+    /// Returns the number of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether a position lies within the grid
+    pub fn contains(&self, [i, j]: [usize; 2]) -> bool {
+        i < self.height && j < self.width
+    }
+
+    /// Returns the tile at a position unless it's outside the grid
+    pub fn get(&self, position: [usize; 2]) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.tiles[position[0] * self.width + position[1]])
+    }
+
+    /// Returns the mutable tile at a position unless it's outside the grid
+    pub fn get_mut(&mut self, position: [usize; 2]) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.tiles[position[0] * self.width + position[1]])
+    }
+
+    /// Returns the position reached by moving `[rows, columns]` from a position
+    /// unless it's outside the grid
+    pub fn offset(&self, [i, j]: [usize; 2], [di, dj]: [isize; 2]) -> Option<[usize; 2]> {
+        let position = [i.checked_add_signed(di)?, j.checked_add_signed(dj)?];
+        self.contains(position).then_some(position)
+    }
+
+    /// Returns the horizontally and vertically adjacent positions within the
+    /// grid (right, up, left, down)
+    pub fn neighbors4(&self, position: [usize; 2]) -> impl Iterator<Item = [usize; 2]> + '_ {
+        [[0, 1], [-1, 0], [0, -1], [1, 0]]
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// Returns the horizontally, vertically and diagonally adjacent positions
+    /// within the grid counterclockwise starting on the right
+    pub fn neighbors8(&self, position: [usize; 2]) -> impl Iterator<Item = [usize; 2]> + '_ {
+        [
+            [0, 1],
+            [-1, 1],
+            [-1, 0],
+            [-1, -1],
+            [0, -1],
+            [1, -1],
+            [1, 0],
+            [1, 1],
+        ]
+        .into_iter()
+        .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// Returns all positions row by row
+    pub fn positions(&self) -> impl Iterator<Item = [usize; 2]> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |i| (0..width).map(move |j| [i, j]))
+    }
+
+    /// Returns all tiles row by row
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.tiles.iter()
+    }
+
+    /// Returns all positions with their tiles row by row
+    pub fn enumerate(&self) -> impl Iterator<Item = ([usize; 2], &T)> {
+        self.positions().zip(self.iter())
+    }
+
+    /// Returns the tiles of a row
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.tiles[i * self.width..(i + 1) * self.width]
+    }
+
+    /// Returns the mutable tiles of a row
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.tiles[i * self.width..(i + 1) * self.width]
+    }
+
+    /// Returns all rows from top to bottom
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.tiles.chunks(self.width.max(1))
+    }
+
+    /// Returns the tiles of a column from top to bottom, panicking if the
+    /// column is outside the grid like [`Grid::row`]
+    pub fn column(&self, j: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(
+            j < self.width,
+            "column {j} outside grid of width {}",
+            self.width
+        );
+        self.tiles[j..].iter().step_by(self.width.max(1))
+    }
+
+    /// Returns all columns from left to right
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + ExactSizeIterator> {
+        (0..self.width).map(|j| self.column(j))
+    }
+
+    /// Returns the first position of a tile row by row
+    pub fn find(&self, tile: &T) -> Option<[usize; 2]>
+    where
+        T: PartialEq,
+    {
+        let index = self.tiles.iter().position(|other| other == tile)?;
+        Some([index / self.width, index % self.width])
+    }
+
+    /// Returns all positions of a tile row by row
+    pub fn find_all<'a>(&'a self, tile: &'a T) -> impl Iterator<Item = [usize; 2]> + 'a
+    where
+        T: PartialEq,
+    {
+        self.enumerate()
+            .filter(move |(_, other)| *other == tile)
+            .map(|(position, _)| position)
+    }
+
+    /// Returns a grid of the same size with each tile converted
+    pub fn map<U>(&self, convert: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            tiles: self.tiles.iter().map(convert).collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Draws a path of adjacent positions with arrows pointing to the next
+    /// position, leaving its last position unchanged
+    pub fn draw_path(&mut self, path: &[[usize; 2]]) {
+        for step in path.windows(2) {
+            if let Some(direction) = Dir4::between(step[0], step[1]) {
+                self[step[0]] = direction.to_arrow();
+            }
+        }
+    }
+}
+
+impl<T> Index<[usize; 2]> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: [usize; 2]) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {position:?} outside of grid"))
+    }
+}
+
+impl<T> IndexMut<[usize; 2]> for Grid<T> {
+    fn index_mut(&mut self, position: [usize; 2]) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {position:?} outside of grid"))
+    }
+}
+
+impl<T: Tile> std::str::FromStr for Grid<T> {
+    type Err = PuzzleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |_, character| T::from_char(character))
+    }
+}
+
+impl<T: Tile> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for tile in row {
+                write!(f, "{}", tile.to_char())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::PuzzleError;
+
+    #[test]
+    fn test_grid() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!((grid[[1, 0]], grid.get([2, 0])), ('d', None));
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert!(grid.column(2).eq(&['c', 'f']));
+        assert_eq!(grid.find(&'e'), Some([1, 1]));
+        assert_eq!(grid.to_string(), "abc\ndef");
+        let neighbors: Vec<_> = grid.neighbors4([0, 0]).collect();
+        assert_eq!(neighbors, [[0, 1], [1, 0]]);
+        assert_eq!(grid.neighbors8([1, 1]).count(), 5);
+        assert_eq!(Grid::from_rows(vec![vec![1], vec![2, 3]]), None);
+        let mut grid = grid;
+        grid.draw_path(&[[1, 0], [0, 0], [0, 1]]);
+        assert_eq!(grid.to_string(), ">bc\n^ef");
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 outside grid of width 3")]
+    fn test_column_outside() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        grid.column(3).count();
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = "12\n3x".parse::<Grid<u8>>().unwrap_err();
+        assert!(matches!(
+            error,
+            PuzzleError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            }
+        ));
+        let error = "12\n3".parse::<Grid<u8>>().unwrap_err();
+        assert!(matches!(
+            error,
+            PuzzleError::Parse {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+    }
+}
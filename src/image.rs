@@ -0,0 +1,367 @@
+//! Pictures of grids written as image files
+//!
+//! Each cell of a grid becomes a square of pixels colored by a [`Palette`]
+//! mapping characters to colors. Pictures are written as PPM or PNG images,
+//! or as SVG drawings, and a [`FrameExporter`] writes every frame of a
+//! visualization into a directory as a numbered sequence.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::Grid;
+use crate::visualize::{Frame, Visualize};
+
+/// Color given by its red, green and blue components
+pub type Color = [u8; 3];
+
+/// Color of empty cells drawn as dots or spaces
+pub const BACKGROUND: Color = [15, 15, 35];
+
+/// Mapping of the characters of cells to colors
+#[derive(Clone, Debug)]
+pub struct Palette {
+    colors: HashMap<char, Color>,
+}
+
+impl Palette {
+    /// Returns a palette with colors for characters commonly used in
+    /// drawings like walls (#), boxes (O), robots (@) and arrows
+    pub fn new() -> Self {
+        let mut colors = HashMap::from([
+            ('.', BACKGROUND),
+            (' ', BACKGROUND),
+            ('#', [200, 200, 210]),
+            ('@', [230, 60, 60]),
+            ('O', [230, 170, 40]),
+            ('[', [230, 170, 40]),
+            (']', [230, 170, 40]),
+            ('X', [250, 220, 70]),
+            ('x', [250, 220, 70]),
+            ('~', [60, 120, 230]),
+            ('H', [230, 60, 60]),
+            ('T', [240, 140, 40]),
+            ('s', [250, 250, 250]),
+        ]);
+        for arrow in ['^', '>', 'v', '<'] {
+            colors.insert(arrow, [80, 210, 120]);
+        }
+        for (k, digit) in ('1'..='9').enumerate() {
+            colors.insert(digit, [0, 100 + 17 * k as u8, 50]);
+        }
+        Self { colors }
+    }
+
+    /// Returns the palette mapping a character to another color
+    pub fn with(mut self, character: char, color: Color) -> Self {
+        self.colors.insert(character, color);
+        self
+    }
+
+    /// Returns the color of a character, which is derived from the character
+    /// itself if it has none assigned
+    pub fn color(&self, character: char) -> Color {
+        self.colors.get(&character).copied().unwrap_or_else(|| {
+            let hash = (character as u32).wrapping_mul(2_654_435_761);
+            [16, 8, 0].map(|shift| (hash >> shift) as u8 | 0x40)
+        })
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns a drawing made of lines of text as grid, padding shorter lines with
+/// spaces
+pub fn text_to_grid(text: &str) -> Grid<char> {
+    let width = text
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let rows = (text.lines())
+        .map(|line| {
+            let mut row: Vec<char> = line.chars().collect();
+            row.resize(width, ' ');
+            row
+        })
+        .collect();
+    Grid::from_rows(rows).expect("rows are padded to the same length")
+}
+
+/// Picture with one color per pixel stored row by row
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Image {
+    /// Returns a grid drawn with squares of `scale` times `scale` pixels per
+    /// cell
+    pub fn from_grid(grid: &Grid<char>, palette: &Palette, scale: usize) -> Self {
+        let (width, height) = (grid.width() * scale, grid.height() * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in grid.rows() {
+            let row_pixels: Vec<_> = (row.iter())
+                .flat_map(|&cell| std::iter::repeat_n(palette.color(cell), scale))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&row_pixels);
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Returns the width in pixels
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height in pixels
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the color of the pixel at `[row, column]`
+    pub fn pixel(&self, [i, j]: [usize; 2]) -> Option<Color> {
+        (i < self.height && j < self.width).then(|| self.pixels[i * self.width + j])
+    }
+
+    /// Returns the image encoded as binary PPM
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flatten());
+        bytes
+    }
+
+    /// Returns the image encoded as PNG
+    pub fn to_png(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        let data: Vec<u8> = self.pixels.iter().flatten().copied().collect();
+        writer.write_image_data(&data).map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+        Ok(bytes)
+    }
+}
+
+/// Returns a grid drawn as SVG with squares of `scale` units per cell
+///
+/// Neighboring cells of the same color in a row are merged into one rectangle
+/// to keep the drawing small.
+pub fn to_svg(grid: &Grid<char>, palette: &Palette, scale: usize) -> String {
+    let (width, height) = (grid.width() * scale, grid.height() * scale);
+    let hex = |[r, g, b]: Color| format!("#{r:02x}{g:02x}{b:02x}");
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">\n\
+         <rect width=\"{width}\" height=\"{height}\" fill=\"{}\"/>\n",
+        hex(BACKGROUND)
+    );
+    for (i, row) in grid.rows().enumerate() {
+        let mut j = 0;
+        while j < row.len() {
+            let color = palette.color(row[j]);
+            let run = row[j..]
+                .iter()
+                .take_while(|&&cell| palette.color(cell) == color)
+                .count();
+            if color != BACKGROUND {
+                svg.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{scale}\" fill=\"{}\"/>\n",
+                    j * scale,
+                    i * scale,
+                    run * scale,
+                    hex(color)
+                ));
+            }
+            j += run;
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// File format of pictures
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// Binary portable pixmap
+    Ppm,
+    /// Portable network graphics
+    Png,
+    /// Scalable vector graphics
+    Svg,
+}
+
+impl Format {
+    /// Returns the usual file extension
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ppm => "ppm",
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+
+    /// Returns a grid drawn in this format
+    pub fn encode(self, grid: &Grid<char>, palette: &Palette, scale: usize) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Ppm => Ok(Image::from_grid(grid, palette, scale).to_ppm()),
+            Self::Png => Image::from_grid(grid, palette, scale).to_png(),
+            Self::Svg => Ok(to_svg(grid, palette, scale).into_bytes()),
+        }
+    }
+}
+
+/// Writes a grid as picture into a file
+pub fn write(
+    path: &Path,
+    grid: &Grid<char>,
+    palette: &Palette,
+    format: Format,
+    scale: usize,
+) -> io::Result<()> {
+    std::fs::write(path, format.encode(grid, palette, scale)?)
+}
+
+/// Visualization writing each frame into a directory as a picture named by
+/// its number like `frame-00001.png`
+pub struct FrameExporter {
+    directory: PathBuf,
+    palette: Palette,
+    format: Format,
+    scale: usize,
+    count: usize,
+    last_frame: Option<Frame>,
+    error: Option<io::Error>,
+}
+
+impl FrameExporter {
+    /// Returns an exporter writing into a directory, which is created if
+    /// necessary
+    pub fn new(directory: impl Into<PathBuf>, format: Format, scale: usize) -> io::Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory,
+            palette: Palette::new(),
+            format,
+            scale,
+            count: 0,
+            last_frame: None,
+            error: None,
+        })
+    }
+
+    /// Returns the exporter coloring cells with another palette
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Returns the last frame written
+    pub fn last_frame(&self) -> Option<&Frame> {
+        self.last_frame.as_ref()
+    }
+
+    /// Returns the number of frames written or the first error which stopped
+    /// the export
+    pub fn finish(&mut self) -> io::Result<usize> {
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => Ok(self.count),
+        }
+    }
+}
+
+impl Visualize for FrameExporter {
+    fn frame(&mut self, draw: &dyn Fn() -> Frame) {
+        if self.error.is_some() {
+            return;
+        }
+        let frame = draw();
+        let name = format!("frame-{:05}.{}", self.count + 1, self.format.extension());
+        let path = self.directory.join(name);
+        match write(
+            &path,
+            &frame.picture,
+            &self.palette,
+            self.format,
+            self.scale,
+        ) {
+            Ok(()) => self.count += 1,
+            Err(error) => self.error = Some(error),
+        }
+        self.last_frame = Some(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BACKGROUND, Format, FrameExporter, Image, Palette, text_to_grid, to_svg};
+    use crate::Grid;
+    use crate::visualize::{Frame, Visualize};
+
+    #[test]
+    fn test_image() {
+        let grid: Grid<char> = "#.\n.@".parse().unwrap();
+        let palette = Palette::new().with('@', [1, 2, 3]);
+        let image = Image::from_grid(&grid, &palette, 2);
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.pixel([1, 1]), Some(palette.color('#')));
+        assert_eq!(image.pixel([0, 2]), Some(BACKGROUND));
+        assert_eq!(image.pixel([3, 3]), Some([1, 2, 3]));
+        assert_eq!(image.pixel([4, 0]), None);
+
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+
+        let png = image.to_png().unwrap();
+        let decoder = png::Decoder::new(std::io::Cursor::new(png));
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (4, 4));
+    }
+
+    #[test]
+    fn test_text_to_grid() {
+        let grid = text_to_grid("#\n.##\n\n");
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.to_string(), "#  \n.##\n   ");
+    }
+
+    #[test]
+    fn test_svg() {
+        let grid: Grid<char> = "##.\n...".parse().unwrap();
+        let svg = to_svg(&grid, &Palette::new(), 10);
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"20\" height=\"10\""));
+    }
+
+    #[test]
+    fn test_frame_exporter() {
+        let directory = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut exporter = FrameExporter::new(&directory, Format::Ppm, 1).unwrap();
+        for step in 0..3 {
+            exporter.frame(&|| Frame::new(Grid::new(step + 1, 1, '#'), format!("step {step}")));
+        }
+        assert_eq!(exporter.finish().unwrap(), 3);
+        assert_eq!(exporter.last_frame().unwrap().caption, "step 2");
+        let last = std::fs::read(directory.join("frame-00003.ppm")).unwrap();
+        assert!(last.starts_with(b"P6\n3 1\n"));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
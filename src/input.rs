@@ -0,0 +1,165 @@
+//! Puzzle input with normalized line endings and common accessors
+
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::{Grid, PuzzleError};
+
+/// Puzzle input text with `\n` line endings and without trailing whitespace on
+/// lines or trailing blank lines
+///
+/// Lines of only whitespace become blank, so they separate blocks like empty
+/// lines.
+///
+/// The input dereferences to its text so all `str` methods are available.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PuzzleInput(String);
+
+impl PuzzleInput {
+    /// Returns the normalized input text
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns an iterator over the lines of the input
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.0.lines()
+    }
+
+    /// Returns an iterator over the blocks of lines separated by blank lines
+    pub fn blocks(&self) -> std::str::Split<'_, &'static str> {
+        self.0.split("\n\n")
+    }
+
+    /// Returns the characters of all lines, which must be of equal length
+    pub fn char_grid(&self) -> Result<Grid<char>, PuzzleError> {
+        self.parse()
+    }
+
+    /// Returns the digits of all lines, which must be of equal length
+    pub fn digit_grid(&self) -> Result<Grid<u8>, PuzzleError> {
+        self.parse()
+    }
+
+    /// Returns all integers found anywhere in the input
+    pub fn numbers<T>(&self) -> Result<Vec<T>, PuzzleError>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.numbers_in(self)
+    }
+
+    /// Returns all integers found in a fragment of the input like a line or
+    /// block
+    ///
+    /// A minus sign counts as part of a number unless it directly follows a
+    /// letter or digit, so `2-4` holds the numbers 2 and 4 while `x=-3` holds
+    /// the number -3.
+    pub fn numbers_in<T>(&self, fragment: &str) -> Result<Vec<T>, PuzzleError>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let bytes = fragment.as_bytes();
+        let mut numbers = Vec::new();
+        let mut index = 0;
+        while index < bytes.len() {
+            let is_sign = bytes[index] == b'-'
+                && bytes.get(index + 1).is_some_and(u8::is_ascii_digit)
+                && (index == 0 || !bytes[index - 1].is_ascii_alphanumeric());
+            if is_sign || bytes[index].is_ascii_digit() {
+                let start = index;
+                index += 1;
+                while index < bytes.len() && bytes[index].is_ascii_digit() {
+                    index += 1;
+                }
+                numbers.push(crate::error::parse(self, &fragment[start..index])?);
+            } else {
+                index += 1;
+            }
+        }
+        Ok(numbers)
+    }
+}
+
+impl From<&str> for PuzzleInput {
+    fn from(text: &str) -> Self {
+        let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        let end = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(0, |i| i + 1);
+        Self(lines[..end].join("\n"))
+    }
+}
+
+impl From<String> for PuzzleInput {
+    fn from(text: String) -> Self {
+        Self::from(text.as_str())
+    }
+}
+
+impl Deref for PuzzleInput {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for PuzzleInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PuzzleInput;
+    use crate::PuzzleError;
+
+    #[test]
+    fn test_normalization() {
+        let input = PuzzleInput::from("1 2 \r\n\t\r\n  3\r\n \r\n\n");
+        assert_eq!(PuzzleInput::from("1\r\n2\r").as_str(), "1\n2");
+        assert_eq!(input.as_str(), "1 2\n\n  3");
+        assert_eq!(input.blocks().collect::<Vec<_>>(), ["1 2", "  3"]);
+        assert_eq!(PuzzleInput::from("\n \n").as_str(), "");
+    }
+
+    #[test]
+    fn test_grids() {
+        let input = PuzzleInput::from("123\n456\n");
+        assert_eq!(input.digit_grid().unwrap().row(0), [1, 2, 3]);
+        assert_eq!(input.char_grid().unwrap().row(1), ['4', '5', '6']);
+        let error = PuzzleInput::from("123\n4x6").digit_grid().unwrap_err();
+        assert!(matches!(
+            error,
+            PuzzleError::Parse {
+                line: 2,
+                column: 2,
+                ..
+            }
+        ));
+        let error = PuzzleInput::from("123\n45").char_grid().unwrap_err();
+        assert!(matches!(
+            error,
+            PuzzleError::Parse {
+                line: 2,
+                column: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_numbers() {
+        let input = PuzzleInput::from("2-4,6-8\np=0,4 v=-3,-10\nx12y");
+        let numbers: Vec<i32> = input.numbers().unwrap();
+        assert_eq!(numbers, [2, 4, 6, 8, 0, 4, -3, -10, 12]);
+        let line = input.lines().nth(1).unwrap();
+        assert_eq!(input.numbers_in::<i32>(line).unwrap(), [0, 4, -3, -10]);
+        assert!(input.numbers::<u8>().is_err());
+    }
+}
@@ -0,0 +1,302 @@
+//! Sets of integers stored as sorted ranges
+//!
+//! Ranges are half-open like `start..end`, so inclusive ranges from puzzle
+//! inputs need their end increased by one.
+
+use std::ops::Range;
+
+use num::PrimInt;
+
+/// Set of integers stored as sorted, disjoint and non-adjacent ranges
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    /// Returns the empty set
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Returns the set's ranges in increasing order
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    /// Returns whether the set has no numbers
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the number of numbers in the set
+    pub fn len(&self) -> T {
+        (self.ranges.iter()).fold(T::zero(), |len, range| len + (range.end - range.start))
+    }
+
+    /// Returns the smallest number unless the set is empty
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// Returns the largest number unless the set is empty
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|range| range.end - T::one())
+    }
+
+    /// Returns the index of the first range ending after a number
+    fn first_ending_after(&self, number: T) -> usize {
+        self.ranges.partition_point(|range| range.end <= number)
+    }
+
+    /// Returns whether the set contains a number
+    pub fn contains(&self, number: T) -> bool {
+        (self.ranges.get(self.first_ending_after(number)))
+            .is_some_and(|range| range.start <= number)
+    }
+
+    /// Returns whether the set contains every number of a range
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        range.is_empty()
+            || (self.ranges.get(self.first_ending_after(range.start)))
+                .is_some_and(|other| other.start <= range.start && range.end <= other.end)
+    }
+
+    /// Returns whether the set contains some number of a range
+    pub fn overlaps(&self, range: &Range<T>) -> bool {
+        !range.is_empty()
+            && (self.ranges.get(self.first_ending_after(range.start)))
+                .is_some_and(|other| other.start < range.end)
+    }
+
+    /// Returns whether every number of the set is in another set
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.ranges.iter().all(|range| other.contains_range(range))
+    }
+
+    /// Returns whether the set has no number in common with another set
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.ranges.iter().all(|range| !other.overlaps(range))
+    }
+
+    /// Adds the numbers of a range merging it with overlapping and adjacent
+    /// ranges
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|other| other.end < range.start);
+        let last = self
+            .ranges
+            .partition_point(|other| other.start <= range.end);
+        let mut merged = range;
+        if first < last {
+            merged.start = merged.start.min(self.ranges[first].start);
+            merged.end = merged.end.max(self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Returns the numbers in either set
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in &other.ranges {
+            union.insert(range.clone());
+        }
+        union
+    }
+
+    /// Returns the numbers in both sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        for range in &self.ranges {
+            for other in &other.ranges[other.first_ending_after(range.start)..] {
+                if other.start >= range.end {
+                    break;
+                }
+                ranges.push(range.start.max(other.start)..range.end.min(other.end));
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Returns the numbers in this set but not in another one
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        for range in &self.ranges {
+            let mut start = range.start;
+            for other in &other.ranges[other.first_ending_after(range.start)..] {
+                if other.start >= range.end {
+                    break;
+                }
+                if start < other.start {
+                    ranges.push(start..other.start);
+                }
+                start = start.max(other.end);
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Returns the set with every number moved by an offset
+    pub fn shift(&self, offset: T) -> Self {
+        let ranges = (self.ranges.iter())
+            .map(|range| range.start + offset..range.end + offset)
+            .collect();
+        Self { ranges }
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/// Mapping of integers moving each range of numbers by its own offset and
+/// leaving numbers outside all ranges unchanged
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalMap<T> {
+    /// Disjoint ranges sorted by start with their offsets
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T: PrimInt> IntervalMap<T> {
+    /// Returns the identity mapping every number to itself
+    pub fn new() -> Self {
+        Self { pieces: Vec::new() }
+    }
+
+    /// Returns the numbers which are moved by a range of the map
+    pub fn domain(&self) -> IntervalSet<T> {
+        self.pieces.iter().map(|(range, _)| range.clone()).collect()
+    }
+
+    /// Adds a range moving its numbers by an offset except for those already
+    /// covered by an earlier range
+    pub fn insert(&mut self, range: Range<T>, offset: T) {
+        let uncovered = IntervalSet::from(range).difference(&self.domain());
+        (self.pieces).extend(uncovered.ranges.into_iter().map(|range| (range, offset)));
+        self.pieces.sort_unstable_by_key(|(range, _)| range.start);
+    }
+
+    /// Returns the number a number is mapped to
+    pub fn get(&self, number: T) -> T {
+        let index = self
+            .pieces
+            .partition_point(|(range, _)| range.end <= number);
+        match self.pieces.get(index) {
+            Some((range, offset)) if range.start <= number => number + *offset,
+            _ => number,
+        }
+    }
+
+    /// Returns the numbers a whole set is mapped to
+    pub fn apply(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut image = set.difference(&self.domain());
+        for (range, offset) in &self.pieces {
+            let moved = set.intersection(&range.clone().into()).shift(*offset);
+            image = image.union(&moved);
+        }
+        image
+    }
+
+    /// Returns every range of the map including the ones left unchanged,
+    /// which cover all numbers but the largest one
+    fn pieces_with_gaps(&self) -> Vec<(Range<T>, T)> {
+        let gaps = IntervalSet::from(T::min_value()..T::max_value()).difference(&self.domain());
+        let mut pieces = self.pieces.clone();
+        pieces.extend(gaps.ranges.into_iter().map(|gap| (gap, T::zero())));
+        pieces.sort_unstable_by_key(|(range, _)| range.start);
+        pieces
+    }
+
+    /// Returns the map applying this map and then another one
+    pub fn then(&self, next: &Self) -> Self {
+        let next_pieces = next.pieces_with_gaps();
+        let mut pieces: Vec<(Range<T>, T)> = Vec::new();
+        for (range, offset) in self.pieces_with_gaps() {
+            let image = range.start + offset..range.end + offset;
+            for (next_range, next_offset) in &next_pieces {
+                let start = image.start.max(next_range.start);
+                let end = image.end.min(next_range.end);
+                if start < end {
+                    pieces.push((start - offset..end - offset, offset + *next_offset));
+                }
+            }
+        }
+        pieces.sort_unstable_by_key(|(range, _)| range.start);
+        let mut merged: Vec<(Range<T>, T)> = Vec::new();
+        for (range, offset) in pieces {
+            match merged.last_mut() {
+                Some((last, last_offset)) if last.end == range.start && *last_offset == offset => {
+                    last.end = range.end;
+                }
+                _ => merged.push((range, offset)),
+            }
+        }
+        merged.retain(|(_, offset)| !offset.is_zero());
+        Self { pieces: merged }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IntervalMap, IntervalSet};
+
+    #[test]
+    fn test_interval_set() {
+        let set: IntervalSet<i32> = [5..8, 0..2, 10..12, 2..3, 7..9].into_iter().collect();
+        assert_eq!(set.ranges(), [0..3, 5..9, 10..12]);
+        assert_eq!((set.len(), set.min(), set.max()), (9, Some(0), Some(11)));
+        assert!(set.contains(8) && !set.contains(9) && !set.contains(-1));
+        assert!(set.contains_range(&(5..9)) && !set.contains_range(&(8..11)));
+        assert!(set.overlaps(&(8..10)) && !set.overlaps(&(9..10)));
+
+        let other = IntervalSet::from(1..11);
+        assert_eq!(set.union(&other), IntervalSet::from(0..12));
+        assert_eq!(set.intersection(&other).ranges(), [1..3, 5..9, 10..11]);
+        assert_eq!(set.difference(&other).ranges(), [0..1, 11..12]);
+        assert_eq!(other.difference(&set).ranges(), [3..5, 9..10]);
+        assert!(IntervalSet::from(6..8).is_subset(&set) && !other.is_subset(&set));
+        assert!(IntervalSet::from(3..5).is_disjoint(&set) && !other.is_disjoint(&set));
+        assert_eq!(set.shift(-1).ranges(), [-1..2, 4..8, 9..11]);
+    }
+
+    #[test]
+    fn test_interval_map() {
+        let mut first = IntervalMap::new();
+        first.insert(0..10, 100);
+        first.insert(5..20, -5);
+        assert_eq!([first.get(4), first.get(12), first.get(20)], [104, 7, 20]);
+        let set = IntervalSet::from(8..22);
+        assert_eq!(first.apply(&set).ranges(), [5..15, 20..22, 108..110]);
+
+        let mut second = IntervalMap::new();
+        second.insert(100..110, -100);
+        let composed = first.then(&second);
+        assert_eq!(
+            [composed.get(4), composed.get(12), composed.get(100)],
+            [4, 7, 0]
+        );
+        for number in -5..30 {
+            assert_eq!(composed.get(number), second.get(first.get(number)));
+        }
+        assert_eq!(composed.apply(&set), second.apply(&first.apply(&set)));
+    }
+}
@@ -4,8 +4,78 @@
 
 #![warn(missing_docs)]
 
+use std::sync::OnceLock;
+
 use chrono::{Datelike, Utc};
-use reqwest::blocking::Client;
+
+// Registers the parts of a puzzle solution from within its module like
+// `crate::register!(2022, 1, "Calorie Counting", 1 => part1, 2 => part2);`
+// optionally followed by `both => both` for a function solving both parts,
+// `show => show` for a function rendering how a part is solved, and
+// `visualize => visualize` for a function solving a part while emitting frames.
+macro_rules! register {
+    (
+        $year:literal, $day:literal, $title:literal
+        $(, $part:literal => $function:path)+
+        $(, both => $both:path)?
+        $(, show => $show:path)?
+        $(, visualize => $visualize:path)? $(,)?
+    ) => {
+        $(::inventory::submit! {
+            $crate::Solution {
+                year: $year,
+                day: $day,
+                part: $part,
+                title: $title,
+                function: $function,
+            }
+        })+
+        $(::inventory::submit! {
+            $crate::CombinedSolution {
+                year: $year,
+                day: $day,
+                function: $both,
+            }
+        })?
+        $(::inventory::submit! {
+            $crate::Rendering {
+                year: $year,
+                day: $day,
+                function: $show,
+            }
+        })?
+        $(::inventory::submit! {
+            $crate::Visualization {
+                year: $year,
+                day: $day,
+                function: $visualize,
+            }
+        })?
+    };
+}
+pub(crate) use register;
+
+pub mod answers;
+pub mod attempts;
+pub mod client;
+pub mod cluster;
+pub mod cycle;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod interval;
+pub mod math;
+pub mod ocr;
+pub mod scaffold;
+pub mod search;
+pub mod visualize;
+pub mod vm;
+pub use client::{Session, Verdict, download_input, submit_answer};
+pub use error::PuzzleError;
+pub use grid::Grid;
+pub use input::PuzzleInput;
 
 /// # Solutions for 2022
 ///
@@ -89,18 +159,107 @@ pub mod aoc2025 {
 }
 
 /// Common return type of puzzle solutions
-pub type PuzzleResult = Result<String, Box<dyn std::error::Error>>;
+pub type PuzzleResult = Result<String, PuzzleError>;
+
+/// Solution for one part of a puzzle as registered by its module
+pub struct Solution {
+    /// Year of the Advent of Code
+    pub year: usize,
+    /// Day of the puzzle between 1 and 25
+    pub day: usize,
+    /// Part of the puzzle (1 or 2)
+    pub part: usize,
+    /// Title of the puzzle
+    pub title: &'static str,
+    /// Function solving the puzzle for a certain input
+    pub function: fn(&PuzzleInput) -> PuzzleResult,
+}
+
+inventory::collect!(Solution);
+
+/// Returns all registered solutions ordered by year, day, and part
+pub fn solutions() -> impl Iterator<Item = &'static Solution> {
+    static SOLUTIONS: OnceLock<Vec<&'static Solution>> = OnceLock::new();
+    SOLUTIONS
+        .get_or_init(|| {
+            let mut solutions: Vec<_> = inventory::iter::<Solution>.into_iter().collect();
+            solutions.sort_unstable_by_key(|s| (s.year, s.day, s.part));
+            solutions
+        })
+        .iter()
+        .copied()
+}
+
+/// Returns the registered solution for a certain puzzle if there is one
+pub fn find_solution(year: usize, day: usize, part: usize) -> Option<&'static Solution> {
+    solutions().find(|s| (s.year, s.day, s.part) == (year, day, part))
+}
+
+/// Solution for both parts of a puzzle sharing parsing and intermediate results
+pub struct CombinedSolution {
+    /// Year of the Advent of Code
+    pub year: usize,
+    /// Day of the puzzle between 1 and 25
+    pub day: usize,
+    /// Function solving both parts of the puzzle for a certain input
+    pub function: fn(&PuzzleInput) -> (PuzzleResult, PuzzleResult),
+}
+
+inventory::collect!(CombinedSolution);
+
+/// Returns the registered combined solution for a certain puzzle if there is one
+pub fn find_combined_solution(year: usize, day: usize) -> Option<&'static CombinedSolution> {
+    inventory::iter::<CombinedSolution>
+        .into_iter()
+        .find(|s| (s.year, s.day) == (year, day))
+}
+
+/// Debug output of a puzzle solution like its path drawn onto the map
+pub struct Rendering {
+    /// Year of the Advent of Code
+    pub year: usize,
+    /// Day of the puzzle between 1 and 25
+    pub day: usize,
+    /// Function rendering the solution of a part for a certain input
+    pub function: fn(&PuzzleInput, usize) -> PuzzleResult,
+}
+
+inventory::collect!(Rendering);
+
+/// Returns the registered rendering for a certain puzzle if there is one
+pub fn find_rendering(year: usize, day: usize) -> Option<&'static Rendering> {
+    inventory::iter::<Rendering>
+        .into_iter()
+        .find(|s| (s.year, s.day) == (year, day))
+}
+
+/// Solution of a puzzle emitting frames of the simulation it runs
+pub struct Visualization {
+    /// Year of the Advent of Code
+    pub year: usize,
+    /// Day of the puzzle between 1 and 25
+    pub day: usize,
+    /// Function solving a part for a certain input while emitting frames
+    pub function: fn(&PuzzleInput, usize, &mut dyn visualize::Visualize) -> PuzzleResult,
+}
+
+inventory::collect!(Visualization);
+
+/// Returns the registered visualization for a certain puzzle if there is one
+pub fn find_visualization(year: usize, day: usize) -> Option<&'static Visualization> {
+    inventory::iter::<Visualization>
+        .into_iter()
+        .find(|s| (s.year, s.day) == (year, day))
+}
 
-/// Returns the input for a certain Advent of Code puzzle or an error if the download fails
-pub fn download_input(session_token: String, year: usize, day: usize) -> Result<String, String> {
-    let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-    Client::new()
-        .get(&url)
-        .header("Cookie", format!("session={session_token}"))
-        .send()
-        .and_then(|response| response.error_for_status())
-        .and_then(|response| response.text())
-        .or(Err(format!("couldn't download from {url}")))
+// Splits both answers or the error of a combined solution into two results
+pub(crate) fn both_results<T: ToString>(
+    result: Result<[T; 2], PuzzleError>,
+) -> (PuzzleResult, PuzzleResult) {
+    match result {
+        Ok([answer1, answer2]) => (Ok(answer1.to_string()), Ok(answer2.to_string())),
+        Err(error) => (Err(error.clone()), Err(error)),
+    }
 }
 
 /// Returns the year of the most recent Advent of Code
@@ -111,125 +270,90 @@ pub fn current_year() -> usize {
 }
 
 /// Solve a certain Advent of Code puzzle
-pub fn solve(year: usize, day: usize, part: usize, input: String) -> PuzzleResult {
-    match (year, day, part) {
-        (2022, 1, 1) => aoc2022::day01::part1(input),
-        (2022, 1, 2) => aoc2022::day01::part2(input),
-        (2022, 2, 1) => aoc2022::day02::part1(input),
-        (2022, 2, 2) => aoc2022::day02::part2(input),
-        (2022, 3, 1) => aoc2022::day03::part1(input),
-        (2022, 3, 2) => aoc2022::day03::part2(input),
-        (2022, 4, 1) => aoc2022::day04::part1(input),
-        (2022, 4, 2) => aoc2022::day04::part2(input),
-        (2022, 5, 1) => aoc2022::day05::part1(input),
-        (2022, 5, 2) => aoc2022::day05::part2(input),
-        (2022, 6, 1) => aoc2022::day06::part1(input),
-        (2022, 6, 2) => aoc2022::day06::part2(input),
-        (2022, 7, 1) => aoc2022::day07::part1(input),
-        (2022, 7, 2) => aoc2022::day07::part2(input),
-        (2022, 8, 1) => aoc2022::day08::part1(input),
-        (2022, 8, 2) => aoc2022::day08::part2(input),
-        (2022, 9, 1) => aoc2022::day09::part1(input),
-        (2022, 9, 2) => aoc2022::day09::part2(input),
-        (2022, 10, 1) => aoc2022::day10::part1(input),
-        (2022, 10, 2) => aoc2022::day10::part2(input),
-        (2022, 11, 1) => aoc2022::day11::part1(input),
-        (2022, 11, 2) => aoc2022::day11::part2(input),
-        (2022, 12, 1) => aoc2022::day12::part1(input),
-        (2022, 12, 2) => aoc2022::day12::part2(input),
-        (2022, 13, 1) => aoc2022::day13::part1(input),
-        (2022, 13, 2) => aoc2022::day13::part2(input),
-
-        (2023, 1, 1) => aoc2023::day01::part1(input),
-        (2023, 1, 2) => aoc2023::day01::part2(input),
-        (2023, 2, 1) => aoc2023::day02::part1(input),
-        (2023, 2, 2) => aoc2023::day02::part2(input),
-        (2023, 3, 1) => aoc2023::day03::part1(input),
-        (2023, 3, 2) => aoc2023::day03::part2(input),
-        (2023, 4, 1) => aoc2023::day04::part1(input),
-        (2023, 4, 2) => aoc2023::day04::part2(input),
-        (2023, 5, 1) => aoc2023::day05::part1(input),
-        (2023, 5, 2) => aoc2023::day05::part2(input),
-        (2023, 6, 1) => aoc2023::day06::part1(input),
-        (2023, 6, 2) => aoc2023::day06::part2(input),
-        (2023, 7, 1) => aoc2023::day07::part1(input),
-        (2023, 7, 2) => aoc2023::day07::part2(input),
-        (2023, 8, 1) => aoc2023::day08::part1(input),
-        (2023, 8, 2) => aoc2023::day08::part2(input),
-        (2023, 9, 1) => aoc2023::day09::part1(input),
-        (2023, 9, 2) => aoc2023::day09::part2(input),
-        (2023, 10, 1) => aoc2023::day10::part1(input),
-        (2023, 10, 2) => aoc2023::day10::part2(input),
-        (2023, 11, 1) => aoc2023::day11::part1(input),
-        (2023, 11, 2) => aoc2023::day11::part2(input),
-        (2023, 12, 1) => aoc2023::day12::part1(input),
-        (2023, 12, 2) => aoc2023::day12::part2(input),
-        (2023, 13, 1) => aoc2023::day13::part1(input),
-        (2023, 13, 2) => aoc2023::day13::part2(input),
-        (2023, 14, 1) => aoc2023::day14::part1(input),
-        (2023, 14, 2) => aoc2023::day14::part2(input),
-        (2023, 15, 1) => aoc2023::day15::part1(input),
-        (2023, 15, 2) => aoc2023::day15::part2(input),
-        (2023, 16, 1) => aoc2023::day16::part1(input),
-        (2023, 16, 2) => aoc2023::day16::part2(input),
-        (2023, 17, 1) => aoc2023::day17::part1(input),
-        (2023, 17, 2) => aoc2023::day17::part2(input),
-        (2023, 18, 1) => aoc2023::day18::part1(input),
-        (2023, 18, 2) => aoc2023::day18::part2(input),
-
-        (2024, 1, 1) => aoc2024::day01::part1(input),
-        (2024, 1, 2) => aoc2024::day01::part2(input),
-        (2024, 2, 1) => aoc2024::day02::part1(input),
-        (2024, 2, 2) => aoc2024::day02::part2(input),
-        (2024, 3, 1) => aoc2024::day03::part1(input),
-        (2024, 3, 2) => aoc2024::day03::part2(input),
-        (2024, 4, 1) => aoc2024::day04::part1(input),
-        (2024, 4, 2) => aoc2024::day04::part2(input),
-        (2024, 5, 1) => aoc2024::day05::part1(input),
-        (2024, 5, 2) => aoc2024::day05::part2(input),
-        (2024, 6, 1) => aoc2024::day06::part1(input),
-        (2024, 6, 2) => aoc2024::day06::part2(input),
-        (2024, 7, 1) => aoc2024::day07::part1(input),
-        (2024, 7, 2) => aoc2024::day07::part2(input),
-        (2024, 8, 1) => aoc2024::day08::part1(input),
-        (2024, 8, 2) => aoc2024::day08::part2(input),
-        (2024, 9, 1) => aoc2024::day09::part1(input),
-        (2024, 9, 2) => aoc2024::day09::part2(input),
-        (2024, 10, 1) => aoc2024::day10::part1(input),
-        (2024, 10, 2) => aoc2024::day10::part2(input),
-        (2024, 11, 1) => aoc2024::day11::part1(input),
-        (2024, 11, 2) => aoc2024::day11::part2(input),
-        (2024, 12, 1) => aoc2024::day12::part1(input),
-        (2024, 12, 2) => aoc2024::day12::part2(input),
-        (2024, 13, 1) => aoc2024::day13::part1(input),
-        (2024, 13, 2) => aoc2024::day13::part2(input),
-        (2024, 14, 1) => aoc2024::day14::part1(input),
-        (2024, 14, 2) => aoc2024::day14::part2(input),
-        (2024, 15, 1) => aoc2024::day15::part1(input),
-        (2024, 15, 2) => aoc2024::day15::part2(input),
-        (2024, 16, 1) => aoc2024::day16::part1(input),
-        (2024, 16, 2) => aoc2024::day16::part2(input),
-        (2024, 17, 1) => aoc2024::day17::part1(input),
-
-        (2025, 1, 1) => aoc2025::day01::part1(input),
-        (2025, 1, 2) => aoc2025::day01::part2(input),
-        (2025, 2, 1) => aoc2025::day02::part1(input),
-        (2025, 2, 2) => aoc2025::day02::part2(input),
-        (2025, 3, 1) => aoc2025::day03::part1(input),
-        (2025, 3, 2) => aoc2025::day03::part2(input),
-        (2025, 4, 1) => aoc2025::day04::part1(input),
-        (2025, 4, 2) => aoc2025::day04::part2(input),
-        (2025, 5, 1) => aoc2025::day05::part1(input),
-        (2025, 5, 2) => aoc2025::day05::part2(input),
-        (2025, 6, 1) => aoc2025::day06::part1(input),
-        (2025, 6, 2) => aoc2025::day06::part2(input),
-        (2025, 7, 1) => aoc2025::day07::part1(input),
-        (2025, 7, 2) => aoc2025::day07::part2(input),
-        (2025, 8, 1) => aoc2025::day08::part1(input),
-        (2025, 8, 2) => aoc2025::day08::part2(input),
-        (2025, 9, 1) => aoc2025::day09::part1(input),
-        (2025, 9, 2) => aoc2025::day09::part2(input),
-
-        (year, day, part) => Err(format!("no solution for day {day} part {part} of {year}"))?,
+pub fn solve(year: usize, day: usize, part: usize, input: &PuzzleInput) -> PuzzleResult {
+    match find_solution(year, day, part) {
+        Some(solution) => (solution.function)(input),
+        None => Err(PuzzleError::NotImplemented { year, day, part }),
+    }
+}
+
+/// Solve both parts of a certain Advent of Code puzzle
+///
+/// Solutions sharing work between the parts are used if registered, otherwise
+/// the parts are solved one after the other.
+pub fn solve_both(year: usize, day: usize, input: &PuzzleInput) -> (PuzzleResult, PuzzleResult) {
+    match find_combined_solution(year, day) {
+        Some(solution) => (solution.function)(input),
+        None => (solve(year, day, 1, input), solve(year, day, 2, input)),
+    }
+}
+
+/// Render the solution of a certain Advent of Code puzzle for debugging
+pub fn show(year: usize, day: usize, part: usize, input: &PuzzleInput) -> PuzzleResult {
+    match (find_rendering(year, day), find_solution(year, day, part)) {
+        (Some(rendering), Some(_)) => (rendering.function)(input, part),
+        _ => Err(PuzzleError::NotImplemented { year, day, part }),
+    }
+}
+
+/// Solve a certain Advent of Code puzzle emitting frames of the simulation
+pub fn solve_visualized(
+    year: usize,
+    day: usize,
+    part: usize,
+    input: &PuzzleInput,
+    visualizer: &mut dyn visualize::Visualize,
+) -> PuzzleResult {
+    match (
+        find_visualization(year, day),
+        find_solution(year, day, part),
+    ) {
+        (Some(visualization), Some(_)) => (visualization.function)(input, part, visualizer),
+        _ => Err(PuzzleError::NotImplemented { year, day, part }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_solutions() {
+        let solutions: Vec<_> = super::solutions().collect();
+        assert!(!solutions.is_empty());
+        for pair in solutions.windows(2) {
+            let [a, b] = [pair[0], pair[1]].map(|s| (s.year, s.day, s.part));
+            assert!(a < b, "{a:?} registered more than once");
+        }
+    }
+
+    #[test]
+    fn test_combined_solutions() {
+        let mut days = Vec::new();
+        for solution in inventory::iter::<super::CombinedSolution> {
+            let (year, day) = (solution.year, solution.day);
+            assert!(
+                !days.contains(&(year, day)),
+                "{year} day {day} registered twice"
+            );
+            for part in [1, 2] {
+                assert!(super::find_solution(year, day, part).is_some());
+            }
+            days.push((year, day));
+        }
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        use super::{PuzzleError, solve};
+        let error = solve(2024, 1, 1, &"3   4\n4   x\n".into()).unwrap_err();
+        assert!(matches!(
+            error,
+            PuzzleError::Parse {
+                line: 2,
+                column: 5,
+                ..
+            }
+        ));
+        let error = solve(2015, 1, 1, &"".into()).unwrap_err();
+        assert!(matches!(error, PuzzleError::NotImplemented { .. }));
     }
 }
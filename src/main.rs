@@ -1,22 +1,59 @@
-use advent_of_code::{current_year, download_input, solve};
-use clap::Parser;
+use std::time::{Duration, Instant};
+
+use advent_of_code::aoc2024::day17;
+use advent_of_code::{
+    PuzzleError, PuzzleInput, Session, Solution, Verdict, answers::Answers, attempts::Attempts,
+    client::DEFAULT_BASE_URL, current_year, download_input, find_rendering, find_solution,
+    find_visualization, image, scaffold, show, solutions, solve_visualized, submit_answer,
+    visualize::Player,
+};
+use clap::{Parser, Subcommand, ValueEnum};
 
 const DEFAULT_TOKEN_PATH: &str = "token.txt";
 const DEFAULT_INPUT_PATH: &str = "input";
+const DEFAULT_ATTEMPTS_PATH: &str = "attempts";
+const DEFAULT_ANSWERS_PATH: &str = "answers";
+const ANSWER_WIDTH: usize = 24;
+const DEBUG_HELP: &str =
+    "Commands: [s]tep (or empty line), [c]ontinue, [b]reak ADDRESS, [d]elete ADDRESS, [q]uit";
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
-    /// Between 1 and 25
-    day: usize,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Between 1 and 25 (all days of the year if omitted)
+    day: Option<usize>,
 
-    /// 1 or 2
-    part: usize,
+    /// 1, 2, or both (both if omitted)
+    #[arg(value_enum)]
+    part: Option<Parts>,
 
     #[arg(short, long, default_value_t = current_year())]
     year: usize,
 
+    /// Range of days like 1-10 instead of a single day
+    #[arg(short, long, value_parser = parse_days, conflicts_with = "day")]
+    days: Option<std::ops::RangeInclusive<usize>>,
+
+    /// 1, 2, or both instead of the positional part
+    #[arg(
+        short,
+        long = "part",
+        value_enum,
+        id = "part_option",
+        value_name = "PART",
+        conflicts_with = "part"
+    )]
+    part_option: Option<Parts>,
+
+    /// Whether to run all solutions of all years
+    #[arg(short, long, conflicts_with_all = ["day", "year", "days"])]
+    all: bool,
+
     /// Path to directory with input files {input}/{year}/{day:02}.txt
-    #[arg(short, long, default_value_t = DEFAULT_INPUT_PATH.to_owned())]
+    #[arg(short, long, global = true, default_value_t = DEFAULT_INPUT_PATH.to_owned())]
     input: String,
 
     /// Path to session token file for downloading input if not found
@@ -26,15 +63,295 @@ struct Args {
     /// Wether to download and overwrite an existing input file
     #[arg(short, long)]
     overwrite: bool,
+
+    /// Whether to submit the solution as answer
+    #[arg(short, long)]
+    submit: bool,
+
+    /// Whether to record the solution as correct answer
+    #[arg(short, long)]
+    record: bool,
+
+    /// Whether to render how the solution was found like its path on the map
+    #[arg(long)]
+    show: bool,
+
+    /// Whether to play the simulation of the solution in the terminal
+    #[arg(long, conflicts_with = "show")]
+    visualize: bool,
+
+    /// Frames per second of the visualization
+    #[arg(long, default_value_t = 10.0, requires = "visualize")]
+    fps: f64,
+
+    /// Path to directory to write the rendering or the frames of the
+    /// visualization into as images
+    #[arg(long, value_name = "DIR", conflicts_with = "visualize")]
+    export_image: Option<String>,
+
+    /// File format of exported images
+    #[arg(long, value_enum, default_value_t = ImageFormat::Png, requires = "export_image")]
+    image_format: ImageFormat,
+
+    /// Pixels per cell of exported images
+    #[arg(long, default_value_t = 4, requires = "export_image")]
+    image_scale: usize,
+
+    /// Path to directory with submitted answers {attempts}/{year}/{day:02}.txt
+    #[arg(long, default_value_t = DEFAULT_ATTEMPTS_PATH.to_owned())]
+    attempts: String,
+
+    /// Path to directory with correct answers {answers}/{year}/{day:02}.toml
+    #[arg(long, global = true, default_value_t = DEFAULT_ANSWERS_PATH.to_owned())]
+    answers: String,
+
+    /// URL of the Advent of Code website
+    #[arg(long, default_value_t = DEFAULT_BASE_URL.to_owned())]
+    base_url: String,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run all solutions on their cached inputs and compare with recorded answers
+    Verify,
+    /// Step through the program of 2024 day 17 stopping at breakpoints
+    Debug {
+        /// Address of an instruction to stop before (may be repeated)
+        #[arg(short, long = "break", value_name = "ADDRESS")]
+        breakpoints: Vec<usize>,
+
+        /// Whether to print the disassembled program and exit
+        #[arg(short, long, conflicts_with = "trace")]
+        disassemble: bool,
+
+        /// Whether to print the registers after each instruction and exit
+        #[arg(short, long)]
+        trace: bool,
+    },
+    /// Create the module of a new puzzle in the crate at the current directory
+    /// using the title and example of its page if saved as
+    /// {input}/{year}/{day:02}.html
+    New {
+        #[arg(short, long, default_value_t = current_year())]
+        year: usize,
+
+        /// Between 1 and 25
+        #[arg(short, long)]
+        day: usize,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Parts {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum ImageFormat {
+    Png,
+    Ppm,
+    Svg,
+}
+
+impl From<ImageFormat> for image::Format {
+    fn from(format: ImageFormat) -> Self {
+        match format {
+            ImageFormat::Png => Self::Png,
+            ImageFormat::Ppm => Self::Ppm,
+            ImageFormat::Svg => Self::Svg,
+        }
+    }
+}
+
+impl Parts {
+    fn contains(&self, part: usize) -> bool {
+        match self {
+            Self::One => part == 1,
+            Self::Two => part == 2,
+            Self::Both => true,
+        }
+    }
+}
+
+fn parse_days(days: &str) -> Result<std::ops::RangeInclusive<usize>, String> {
+    let parse = |day: &str| day.trim().parse().or(Err(format!("invalid day {day:?}")));
+    Ok(match days.split_once('-') {
+        Some((first, last)) => parse(first)?..=parse(last)?,
+        None => parse(days)?..=parse(days)?,
+    })
+}
+
+/// Shortens an answer to fit into the table
+fn answer_cell(answer: &str) -> String {
+    let mut lines = answer.lines();
+    let first_line = lines.next().unwrap_or_default();
+    if first_line.chars().count() > ANSWER_WIDTH || lines.next().is_some() {
+        let shortened: String = first_line.chars().take(ANSWER_WIDTH - 1).collect();
+        format!("{shortened}…")
+    } else {
+        first_line.to_owned()
+    }
+}
+
+/// Runs solutions on their input files printing a table with answers, times,
+/// and statuses and returns whether all of them succeeded
+///
+/// Answers are compared with the recorded ones if `compare` is set, in which
+/// case mismatches count as failure.
+fn run_table(
+    args: &Args,
+    solutions: impl Iterator<Item = &'static Solution>,
+    compare: bool,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    println!(
+        "Year Day Part  {:ANSWER_WIDTH$}  {:>10}  Status",
+        "Answer", "Time"
+    );
+    let mut counts = std::collections::BTreeMap::new();
+    let mut notes = Vec::new();
+    let mut year_time: Option<(usize, Duration)> = None;
+    let print_year_time = |(year, time): (usize, Duration)| {
+        println!("{year} total      {:ANSWER_WIDTH$}  {time:>10.2?}", "");
+    };
+    let mut input = None;
+    for solution in solutions {
+        let (year, day, part) = (solution.year, solution.day, solution.part);
+        if let Some(previous) = year_time.filter(|&(previous_year, _)| previous_year != year) {
+            print_year_time(previous);
+            year_time = None;
+        }
+        let (_, year_total) = year_time.get_or_insert((year, Duration::ZERO));
+        let path = format!("{}/{year}/{day:02}.txt", args.input);
+        if input
+            .as_ref()
+            .is_none_or(|(input_path, _)| input_path != &path)
+        {
+            let text = std::fs::read_to_string(&path).ok();
+            input = Some((path.clone(), text.map(PuzzleInput::from)));
+        }
+        let (answer, time, status) = if let Some((_, Some(input))) = &input {
+            let start = Instant::now();
+            let result = (solution.function)(input);
+            let time = start.elapsed();
+            *year_total += time;
+            let expected = (compare.then(|| Answers::load(&args.answers, year, day)))
+                .transpose()
+                .map(|answers| answers.map(|answers| answers.get(part).map(str::to_owned)));
+            match (result, expected) {
+                (Err(error), _) => {
+                    notes.push(format!("{year} day {day} part {part}: {error}"));
+                    (String::new(), Some(time), "error")
+                }
+                (Ok(answer), Err(error)) => {
+                    notes.push(format!("{year} day {day} part {part}: {error}"));
+                    (answer, Some(time), "error")
+                }
+                (Ok(answer), Ok(None)) => (answer, Some(time), "ok"),
+                (Ok(answer), Ok(Some(Some(expected)))) if answer == expected => {
+                    (answer, Some(time), "ok")
+                }
+                (Ok(answer), Ok(Some(Some(expected)))) => {
+                    let note = format!("{year} day {day} part {part}: expected {expected}");
+                    notes.push(note);
+                    (answer, Some(time), "mismatch")
+                }
+                (Ok(answer), Ok(Some(None))) => (answer, Some(time), "unrecorded"),
+            }
+        } else {
+            (String::new(), None, "missing input")
+        };
+        let time = time.map_or(String::new(), |time| format!("{time:.2?}"));
+        let answer = answer_cell(&answer);
+        println!("{year} {day:3} {part:4}  {answer:ANSWER_WIDTH$}  {time:>10}  {status}");
+        *counts.entry(status).or_insert(0) += 1;
+    }
+    if let Some(last) = year_time {
+        print_year_time(last);
+    }
+    for note in notes {
+        println!("\n{note}");
+    }
+    let summary: Vec<_> = (counts.iter())
+        .map(|(status, count)| format!("{count} {status}"))
+        .collect();
+    println!("\n{}", summary.join(", "));
+    Ok(!counts.contains_key("mismatch") && !counts.contains_key("error"))
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse command-line arguments
     let args = Args::parse();
-    if ![1, 2].contains(&args.part) {
-        Err(format!("part must be 1 or 2 not {}", args.part))?
+    let parts = args.part.or(args.part_option);
+    let success = match (&args.command, args.day, parts) {
+        (Some(Command::Verify), _, _) => run_table(&args, solutions(), true)?,
+        (Some(Command::Debug { .. }), _, _) => debug(&args)?,
+        (Some(Command::New { year, day }), _, _) => new_day(&args, *year, *day)?,
+        (None, Some(day), Some(Parts::One)) if !args.all => solve_one(&args, day, 1)?,
+        (None, Some(day), Some(Parts::Two)) if !args.all => solve_one(&args, day, 2)?,
+        (None, day, parts) => {
+            if args.submit || args.record {
+                Err("submitting and recording requires a single day and part")?
+            }
+            if args.show {
+                Err("showing requires a single day and part")?
+            }
+            if args.visualize {
+                Err("visualizing requires a single day and part")?
+            }
+            if args.export_image.is_some() {
+                Err("exporting images requires a single day and part")?
+            }
+            let days = (args.days.clone()).or(day.map(|day| day..=day));
+            let selected = solutions().filter(|s| {
+                (args.all || s.year == args.year)
+                    && days.as_ref().is_none_or(|days| days.contains(&s.day))
+                    && parts.is_none_or(|parts| parts.contains(s.part))
+            });
+            run_table(&args, selected, false)?
+        }
+    };
+    if !success {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Solves one part of a puzzle downloading the input if necessary and returns
+/// whether it succeeded
+fn solve_one(args: &Args, day: usize, part: usize) -> Result<bool, Box<dyn std::error::Error>> {
+    let solution = find_solution(args.year, day, part).ok_or(PuzzleError::NotImplemented {
+        year: args.year,
+        day,
+        part,
+    })?;
+    if args.show && find_rendering(args.year, day).is_none() {
+        Err(format!("no rendering for day {day} of {}", args.year))?
+    }
+    if args.visualize && find_visualization(args.year, day).is_none() {
+        Err(format!("no visualization for day {day} of {}", args.year))?
+    }
+    let exports_frames =
+        args.export_image.is_some() && find_visualization(args.year, day).is_some();
+    if args.export_image.is_some() && !exports_frames && find_rendering(args.year, day).is_none() {
+        Err(format!(
+            "no rendering or visualization for day {day} of {}",
+            args.year
+        ))?
     }
-    let path = format!("{}/{}/{:02}.txt", args.input, args.year, args.day);
+    println!(
+        "Day {}: {} (part {})",
+        solution.day, solution.title, solution.part
+    );
+    let path = format!("{}/{}/{day:02}.txt", args.input, args.year);
+    let session = || {
+        std::fs::read_to_string(&args.token)
+            .or(Err(format!("couldn't read token from {}", args.token)))
+            .map(|token| Session::new(&token).with_base_url(&args.base_url))
+    };
 
     // Read or download puzzle input
     let input = if !args.overwrite && std::path::Path::new(&path).exists() {
@@ -42,9 +359,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         std::fs::read_to_string(&path).or(Err(format!("couldn't read {path}")))
     } else {
         println!("Downloading input into {path}");
-        std::fs::read_to_string(&args.token)
-            .or(Err(format!("couldn't read token from {}", args.token)))
-            .and_then(|token| download_input(token, args.year, args.day))
+        session()
+            .and_then(|session| download_input(&session, args.year, day))
             .inspect(|input| {
                 // Try to write puzzle input
                 if std::path::Path::new(&path)
@@ -64,5 +380,192 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }?;
 
     // Solve puzzle
-    solve(args.year, args.day, args.part, input).map(|solution| println!("Solution: {solution}"))
+    let input = PuzzleInput::from(input);
+    let format = image::Format::from(args.image_format);
+    let still = |directory: &str| {
+        let name = format!("{}-{day:02}-{part}.{}", args.year, format.extension());
+        std::path::Path::new(directory).join(name)
+    };
+    let result = if args.visualize {
+        let mut player = Player::new(args.fps);
+        solve_visualized(args.year, day, part, &input, &mut player)
+    } else if let Some(directory) = args.export_image.as_deref()
+        && exports_frames
+    {
+        let mut exporter = image::FrameExporter::new(directory, format, args.image_scale)?;
+        let result = solve_visualized(args.year, day, part, &input, &mut exporter);
+        println!("Wrote {} frames into {directory}", exporter.finish()?);
+        if let Some(frame) = exporter.last_frame() {
+            let path = still(directory);
+            let palette = image::Palette::new();
+            image::write(&path, &frame.picture, &palette, format, args.image_scale)?;
+            println!("Wrote last frame to {}", path.display());
+        }
+        result
+    } else {
+        (solution.function)(&input)
+    };
+    let answer = match result {
+        Ok(answer) => answer,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            return Ok(false);
+        }
+    };
+    println!("Solution: {answer}");
+    if args.show {
+        match show(args.year, day, part, &input) {
+            Ok(rendering) => println!("{rendering}"),
+            Err(error) => eprintln!("Error: {error}"),
+        }
+    }
+    if let Some(directory) = args.export_image.as_deref()
+        && !exports_frames
+    {
+        match show(args.year, day, part, &input) {
+            Ok(rendering) => {
+                std::fs::create_dir_all(directory)?;
+                let path = still(directory);
+                let picture = image::text_to_grid(&rendering);
+                let palette = image::Palette::new();
+                image::write(&path, &picture, &palette, format, args.image_scale)?;
+                println!("Wrote rendering to {}", path.display());
+            }
+            Err(error) => eprintln!("Error: {error}"),
+        }
+    }
+
+    // Submit answer unless previous attempts tell it's wrong
+    let mut record = args.record;
+    if args.submit {
+        let mut attempts = Attempts::load(&args.attempts, args.year, day)?;
+        attempts.check(part, &answer)?;
+        let verdict = submit_answer(&session()?, args.year, day, part, &answer)?;
+        println!("Verdict: {verdict}");
+        attempts.record(part, &answer, verdict)?;
+        record |= verdict == Verdict::Correct;
+    }
+
+    // Record correct answer
+    if record {
+        let mut answers = Answers::load(&args.answers, args.year, day)?;
+        answers.record(part, &answer)?;
+        println!("Recorded answer in {}", args.answers);
+    }
+    Ok(true)
+}
+
+/// Creates the module of a new puzzle and declares it in the crate root, both
+/// found from the current directory, and returns whether it succeeded
+fn new_day(args: &Args, year: usize, day: usize) -> Result<bool, Box<dyn std::error::Error>> {
+    if !(1..=25).contains(&day) {
+        Err(format!("day {day} isn't between 1 and 25"))?
+    }
+    let lib_path = "src/lib.rs";
+    let Ok(lib) = std::fs::read_to_string(lib_path) else {
+        Err(format!(
+            "{lib_path} not found, run from the root of the crate"
+        ))?
+    };
+    let module_path = std::path::PathBuf::from(format!("src/aoc{year}/day{day:02}.rs"));
+    if module_path.exists() {
+        Err(format!("{} already exists", module_path.display()))?
+    }
+    let lib = scaffold::declare_module(&lib, year, day)?;
+
+    let page_path = format!("{}/{year}/{day:02}.html", args.input);
+    let puzzle = match std::fs::read_to_string(&page_path) {
+        Ok(page) => {
+            println!("Reading puzzle page from {page_path}");
+            scaffold::Puzzle::from_page(&page)
+        }
+        Err(_) => {
+            println!("No puzzle page at {page_path}, leaving title and example empty");
+            scaffold::Puzzle::default()
+        }
+    };
+    if let Some(parent) = module_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&module_path, scaffold::module_source(year, day, &puzzle))?;
+    println!("Created {}", module_path.display());
+    let formatted = std::process::Command::new("rustfmt")
+        .args(["--edition", "2024"])
+        .arg(&module_path)
+        .status();
+    if !formatted.is_ok_and(|status| status.success()) {
+        eprintln!("Warning: couldn't format {}", module_path.display());
+    }
+    // Remove the module again so that it can be created by running again
+    if let Err(error) = std::fs::write(lib_path, lib) {
+        std::fs::remove_file(&module_path)?;
+        Err(format!("couldn't write {lib_path}: {error}"))?
+    }
+    println!("Declared aoc{year}::day{day:02} in {lib_path}");
+    Ok(true)
+}
+
+/// Runs the program of 2024 day 17 on its cached input either printing its
+/// disassembly or trace, or stepping through it with commands read from the
+/// standard input, and returns whether it succeeded
+fn debug(args: &Args) -> Result<bool, Box<dyn std::error::Error>> {
+    let Some(Command::Debug {
+        breakpoints,
+        disassemble,
+        trace,
+    }) = &args.command
+    else {
+        return Ok(false);
+    };
+    let path = format!("{}/2024/17.txt", args.input);
+    let input = std::fs::read_to_string(&path).or(Err(format!("couldn't read {path}")))?;
+    let input = PuzzleInput::from(input);
+    if *disassemble {
+        println!("{}", day17::disassemble(&input)?);
+        return Ok(true);
+    }
+    if *trace {
+        println!("{}", day17::trace(&input)?);
+        return Ok(true);
+    }
+
+    let mut debugger = day17::Debugger::new(&input)?;
+    for &address in breakpoints {
+        debugger.add_breakpoint(address);
+    }
+    println!("{}\n", day17::disassemble(&input)?);
+    println!("{DEBUG_HELP}\n\n{}", debugger.state());
+    let mut lines = std::io::stdin().lines();
+    while !debugger.is_halted() {
+        use std::io::Write;
+        print!("> ");
+        std::io::stdout().flush()?;
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line?;
+        let words: Vec<_> = line.split_whitespace().collect();
+        let address = words.get(1).and_then(|address| address.parse().ok());
+        match (&words[..], address) {
+            ([] | ["s" | "step"], _) => debugger.step()?,
+            (["c" | "continue"], _) => debugger.resume()?,
+            (["b" | "break", _], Some(address)) => {
+                debugger.add_breakpoint(address);
+                continue;
+            }
+            (["d" | "delete", _], Some(address)) => {
+                if !debugger.remove_breakpoint(address) {
+                    println!("No breakpoint at {address}");
+                }
+                continue;
+            }
+            (["q" | "quit"], _) => break,
+            _ => {
+                println!("{DEBUG_HELP}");
+                continue;
+            }
+        }
+        println!("{}", debugger.state());
+    }
+    Ok(true)
 }
@@ -0,0 +1,140 @@
+//! Number theory and exact linear algebra on integers
+
+use num::rational::Rational64;
+
+/// Returns `(g, x, y)` where `g` is the non-negative greatest common divisor of
+/// `a` and `b` and `a * x + b * y == g`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Returns the inverse of `a` modulo `modulus` in `0..modulus` unless they
+/// have a common divisor
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (gcd, x, _) = extended_gcd(a, modulus);
+    (gcd == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Returns a solution `[x, y]` of `a * x + b * y == c` and a step `[dx, dy]`
+/// such that the solutions are exactly `[x + k * dx, y + k * dy]` for all
+/// integers `k` unless there is none or both `a` and `b` are zero
+pub fn diophantine(a: i64, b: i64, c: i64) -> Option<([i64; 2], [i64; 2])> {
+    let (gcd, x, y) = extended_gcd(a, b);
+    if gcd == 0 || c % gcd != 0 {
+        return None;
+    }
+    let factor = c / gcd;
+    Some(([x * factor, y * factor], [b / gcd, -a / gcd]))
+}
+
+/// Returns the residue and modulus `(r, m)` such that the numbers congruent
+/// to all given `(residue, modulus)` pairs are exactly the ones congruent to
+/// `r` modulo `m` with `r` in `0..m`
+///
+/// Moduli needn't be coprime, in which case `m` is their least common multiple
+/// and there is no solution if the congruences contradict each other. Moduli
+/// must be positive, so there is no solution if any is less than 1, and no
+/// congruences at all give `(0, 1)`. Intermediate products are computed with
+/// 128 bits, so only `m` needs to fit into 64 bits.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let mut solution = (0, 1);
+    for (residue, modulus) in congruences {
+        if modulus < 1 {
+            return None;
+        }
+        let (r, m) = solution;
+        let (gcd, x, _) = extended_gcd(m, modulus);
+        let difference = residue - r;
+        if difference % gcd != 0 {
+            return None;
+        }
+        // r + m * k ≡ residue (mod modulus) is solved by k = x * difference / gcd
+        let lcm = m / gcd * modulus;
+        let k = (x as i128 * (difference / gcd) as i128).rem_euclid((modulus / gcd) as i128);
+        let r = (r as i128 + m as i128 * k).rem_euclid(lcm as i128);
+        solution = (r as i64, lcm);
+    }
+    Some(solution)
+}
+
+/// Returns the exact solution `[x, y]` of the linear system
+/// `matrix * [x, y] == rhs` unless the matrix is singular
+pub fn solve_2x2(matrix: [[i64; 2]; 2], rhs: [i64; 2]) -> Option<[Rational64; 2]> {
+    let [[a, b], [c, d]] = matrix;
+    let determinant = a * d - b * c;
+    if determinant == 0 {
+        return None;
+    }
+    let x = Rational64::new(d * rhs[0] - b * rhs[1], determinant);
+    let y = Rational64::new(a * rhs[1] - c * rhs[0], determinant);
+    Some([x, y])
+}
+
+#[cfg(test)]
+mod tests {
+    use num::rational::Rational64;
+
+    use super::{crt, diophantine, extended_gcd, mod_inverse, solve_2x2};
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6), (2, 1, 1));
+        assert_eq!(extended_gcd(0, -5), (5, 0, -1));
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 10), None);
+    }
+
+    #[test]
+    fn test_diophantine() {
+        let ([x, y], [dx, dy]) = diophantine(6, -4, 10).unwrap();
+        for k in -3..3 {
+            assert_eq!(6 * (x + k * dx) - 4 * (y + k * dy), 10);
+        }
+        assert_eq!((dx.abs(), dy.abs()), (2, 3));
+        assert_eq!(diophantine(6, 4, 3), None);
+        assert_eq!(diophantine(0, 0, 0), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(3, 4), (4, 6)]), None);
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(2, 3), (0, 0)]), None);
+        assert_eq!(crt([(0, -4)]), None);
+        let big = 1_000_000_007;
+        assert_eq!(
+            crt([(1, big), (0, big + 2)]),
+            Some((500000008500000036, big * (big + 2)))
+        );
+    }
+
+    #[test]
+    fn test_solve_2x2() {
+        let solution = solve_2x2([[94, 22], [34, 67]], [8400, 5400]);
+        assert_eq!(solution, Some([80.into(), 40.into()]));
+        let solution = solve_2x2([[2, 1], [1, 3]], [1, 1]);
+        assert_eq!(
+            solution,
+            Some([Rational64::new(2, 5), Rational64::new(1, 5)])
+        );
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 6]), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Recognition of capital letters drawn with pixels
+//!
+//! Some puzzles have answers drawn as letters made of lit pixels `#` on a
+//! background of unlit pixels like `.` instead of text. The letters use one
+//! of two fonts, one 6 pixels high and one 10 pixels high, and are separated
+//! by columns of unlit pixels.
+
+use crate::PuzzleError;
+
+/// Letters of the font 6 pixels high in the order they are drawn in
+const SMALL_LETTERS: &str = "ABCEFGHIJKLOPRSUYZ";
+
+const SMALL_FONT: [&str; 6] = [
+    ".##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####",
+    "#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#",
+    "#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.",
+    "####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..",
+    "#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...",
+    "#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####",
+];
+
+/// Letters of the font 10 pixels high in the order they are drawn in
+const LARGE_LETTERS: &str = "ABCEFGHJKLNPRXZ";
+
+const LARGE_FONT: [&str; 10] = [
+    "..##...#####...####..######.######..####..#....#....###.#....#.#......#....#.#####..#####..#....#.######",
+    ".#..#..#....#.#....#.#......#......#....#.#....#.....#..#...#..#......##...#.#....#.#....#.#....#......#",
+    "#....#.#....#.#......#......#......#......#....#.....#..#..#...#......##...#.#....#.#....#..#..#.......#",
+    "#....#.#....#.#......#......#......#......#....#.....#..#.#....#......#.#..#.#....#.#....#..#..#......#.",
+    "#....#.#####..#......#####..#####..#......######.....#..##.....#......#.#..#.#####..#####....##......#..",
+    "######.#....#.#......#......#......#..###.#....#.....#..##.....#......#..#.#.#......#..#.....##.....#...",
+    "#....#.#....#.#......#......#......#....#.#....#.....#..#.#....#......#..#.#.#......#...#...#..#...#....",
+    "#....#.#....#.#......#......#......#....#.#....#.#...#..#..#...#......#...##.#......#...#...#..#..#.....",
+    "#....#.#....#.#....#.#......#......#...##.#....#.#...#..#...#..#......#...##.#......#....#.#....#.#.....",
+    "#....#.#####...####..######.#.......###.#.#....#..###...#....#.######.#....#.#......#....#.#....#.######",
+];
+
+/// Rows of pixels telling whether they are lit
+type Pixels = Vec<Vec<bool>>;
+
+fn pixels<'a>(rows: impl IntoIterator<Item = &'a str>) -> Pixels {
+    let rows = rows.into_iter();
+    rows.map(|row| row.chars().map(|c| c == '#').collect())
+        .collect()
+}
+
+/// Splits rows of pixels at columns without lit pixels and returns the parts
+/// in between with the columns they start at
+fn glyphs(rows: &[Vec<bool>]) -> Vec<(usize, Pixels)> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let is_lit = |row: &Vec<bool>, column| row.get(column).copied().unwrap_or(false);
+    let is_blank = |column| !rows.iter().any(|row| is_lit(row, column));
+    let mut glyphs = Vec::new();
+    let mut column = 0;
+    while column < width {
+        if is_blank(column) {
+            column += 1;
+            continue;
+        }
+        let start = column;
+        while column < width && !is_blank(column) {
+            column += 1;
+        }
+        let glyph = (rows.iter())
+            .map(|row| (start..column).map(|c| is_lit(row, c)).collect())
+            .collect();
+        glyphs.push((start, glyph));
+    }
+    glyphs
+}
+
+/// Returns the text drawn with `#` for lit pixels
+///
+/// Rows without lit pixels above and below the letters are ignored.
+pub fn recognize(art: &str) -> Result<String, PuzzleError> {
+    let rows = pixels(art.lines());
+    let (Some(first), Some(last)) = (
+        rows.iter().position(|row| row.contains(&true)),
+        rows.iter().rposition(|row| row.contains(&true)),
+    ) else {
+        return Err(PuzzleError::MissingData("lit pixels".to_owned()));
+    };
+    let rows = &rows[first..=last];
+    let (font, letters) = match rows.len() {
+        6 => (pixels(SMALL_FONT), SMALL_LETTERS),
+        10 => (pixels(LARGE_FONT), LARGE_LETTERS),
+        height => Err(PuzzleError::UnsupportedInput(format!(
+            "letters {height} pixels high"
+        )))?,
+    };
+    let font: Vec<_> = (glyphs(&font).into_iter())
+        .map(|(_, glyph)| glyph)
+        .zip(letters.chars())
+        .collect();
+    (glyphs(rows).into_iter())
+        .map(|(column, glyph)| {
+            (font.iter())
+                .find(|(pixels, _)| *pixels == glyph)
+                .map(|&(_, letter)| letter)
+                .ok_or_else(|| {
+                    PuzzleError::UnsupportedInput(format!("unknown letter at column {column}"))
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LARGE_FONT, LARGE_LETTERS, SMALL_FONT, SMALL_LETTERS, recognize};
+
+    #[test]
+    fn test_fonts() {
+        assert_eq!(recognize(&SMALL_FONT.join("\n")).unwrap(), SMALL_LETTERS);
+        assert_eq!(recognize(&LARGE_FONT.join("\n")).unwrap(), LARGE_LETTERS);
+    }
+
+    #[test]
+    fn test_recognize() {
+        let art = concat!(
+            "\n",
+            "###..#....###...##..####.###...##..#....\n",
+            "#..#.#....#..#.#..#.#....#..#.#..#.#....\n",
+            "#..#.#....#..#.#..#.###..###..#....#....\n",
+            "###..#....###..####.#....#..#.#....#....\n",
+            "#....#....#....#..#.#....#..#.#..#.#....\n",
+            "#....####.#....#..#.#....###...##..####.",
+        );
+        assert_eq!(recognize(art).unwrap(), "PLPAFBCL");
+        assert!(recognize("#.#\n.#.\n#.#\n.#.\n#.#\n.#.").is_err());
+        assert!(recognize("....\n....").is_err());
+    }
+}
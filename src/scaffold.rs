@@ -0,0 +1,232 @@
+//! Generation of modules for new puzzles
+//!
+//! A new day starts as a module in the house style with a header linking the
+//! puzzle, both parts returning [`NotImplemented`](crate::PuzzleError) and
+//! tests on the example. The module only needs to be declared in the module
+//! tree since solutions register themselves and benchmarks run all registered
+//! solutions. The title and the example are taken from the puzzle page when it
+//! was saved.
+
+use regex::Regex;
+
+/// Details of a puzzle found on its page
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Puzzle {
+    /// Title like "Calorie Counting"
+    pub title: Option<String>,
+    /// Example input, which is the first preformatted block on the page
+    pub example: Option<String>,
+}
+
+impl Puzzle {
+    /// Returns the title and the example found in the HTML of a puzzle page
+    pub fn from_page(html: &str) -> Self {
+        let title = Regex::new(r"<h2>--- Day \d+: (.*?) ---</h2>").unwrap();
+        let example = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+        Self {
+            title: (title.captures(html)).map(|captures| text(&captures[1])),
+            example: (example.captures(html)).map(|captures| text(&captures[1])),
+        }
+    }
+}
+
+/// Returns the text of HTML with tags removed and entities replaced
+fn text(html: &str) -> String {
+    let tag = Regex::new(r"<[^>]*>").unwrap();
+    (tag.replace_all(html, ""))
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Returns a string literal of the example as the tests of modules write it,
+/// split into one line per literal if it has several lines
+fn example_literal(example: &str) -> String {
+    let lines: Vec<_> = example.trim_end_matches('\n').lines().collect();
+    if lines.len() <= 1 {
+        return format!("{:?}", lines.concat());
+    }
+    let mut literal = "concat!(\n".to_owned();
+    for (k, line) in lines.iter().enumerate() {
+        if k + 1 < lines.len() {
+            literal.push_str(&format!("        {:?},\n", format!("{line}\n")));
+        } else {
+            literal.push_str(&format!("        {line:?}\n"));
+        }
+    }
+    literal.push_str("    )");
+    literal
+}
+
+/// Returns the source of the module solving a puzzle
+pub fn module_source(year: usize, day: usize, puzzle: &Puzzle) -> String {
+    let title = puzzle.title.as_deref().unwrap_or("TODO");
+    let example = example_literal(puzzle.example.as_deref().unwrap_or(""));
+    let part = |part: usize| {
+        format!(
+            "/// Part {part}: TODO\n\
+             pub fn part{part}(_input: &crate::PuzzleInput) -> crate::PuzzleResult {{\n    \
+                 Err(crate::PuzzleError::NotImplemented {{\n        \
+                     year: {year},\n        \
+                     day: {day},\n        \
+                     part: {part},\n    \
+                 }})\n\
+             }}\n"
+        )
+    };
+    let test = |part: usize| {
+        format!(
+            "    #[test]\n    \
+                 fn test_part{part}() {{\n        \
+                     assert_eq!(&super::part{part}(&INPUT.into()).unwrap(), \"TODO\");\n    \
+                 }}\n"
+        )
+    };
+    format!(
+        "//! # Day {day}: {title}\n\
+         //!\n\
+         //! TODO: Describe the input.\n\
+         //!\n\
+         //! [puzzle site](https://adventofcode.com/{year}/day/{day})\n\
+         \n\
+         {}\n\
+         {}\n\
+         crate::register!({year}, {day}, {title:?}, 1 => part1, 2 => part2);\n\
+         \n\
+         #[cfg(test)]\n\
+         mod tests {{\n    \
+             const INPUT: &str = {example};\n\
+         \n\
+         {}\n\
+         {}\
+         }}\n",
+        part(1),
+        part(2),
+        test(1),
+        test(2),
+    )
+}
+
+/// Returns the source of the crate root with the module of a puzzle declared
+/// in the module of its year, which is added after the other years if missing
+pub fn declare_module(lib: &str, year: usize, day: usize) -> Result<String, String> {
+    let declaration = format!("    pub mod day{day:02};\n");
+    let block_start = format!("pub mod aoc{year} {{\n");
+    let Some(start) = lib
+        .find(&block_start)
+        .map(|start| start + block_start.len())
+    else {
+        let last_block =
+            (lib.rfind("pub mod aoc")).ok_or("no module of a year in the crate root".to_owned())?;
+        let end = last_block + lib[last_block..].find("\n}\n").ok_or("unclosed module")? + 3;
+        let block = format!(
+            "\n/// # Solutions for {year}\n\
+             ///\n\
+             /// [puzzle site](https://adventofcode.com/{year})\n\
+             {block_start}{declaration}}}\n"
+        );
+        return Ok(format!("{}{block}{}", &lib[..end], &lib[end..]));
+    };
+    let end = start + lib[start..].find("}\n").ok_or("unclosed module")?;
+    let mut position = start;
+    for line in lib[start..end].split_inclusive('\n') {
+        if line == declaration {
+            return Err(format!("module aoc{year}::day{day:02} already declared"));
+        }
+        if line.trim_start().starts_with("pub mod day") && *line < *declaration {
+            position += line.len();
+        }
+    }
+    Ok(format!(
+        "{}{declaration}{}",
+        &lib[..position],
+        &lib[position..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Puzzle, declare_module, module_source};
+
+    const LIB: &str = concat!(
+        "pub mod grid;\n",
+        "\n",
+        "/// # Solutions for 2024\n",
+        "///\n",
+        "/// [puzzle site](https://adventofcode.com/2024)\n",
+        "pub mod aoc2024 {\n",
+        "    pub mod day01;\n",
+        "    pub mod day03;\n",
+        "}\n",
+        "\n",
+        "/// Common return type of puzzle solutions\n",
+    );
+
+    #[test]
+    fn test_from_page() {
+        let html = concat!(
+            "<article class=\"day-desc\"><h2>--- Day 3: Mull It Over ---</h2>",
+            "<p>For example, consider the following section:</p>\n",
+            "<pre><code>x<em>mul(2,4)</em>&amp;&lt;don't&gt;\n",
+            "do()\n</code></pre>\n",
+            "<pre><code>ignored</code></pre></article>",
+        );
+        let puzzle = Puzzle::from_page(html);
+        assert_eq!(puzzle.title.as_deref(), Some("Mull It Over"));
+        assert_eq!(puzzle.example.as_deref(), Some("xmul(2,4)&<don't>\ndo()\n"));
+        assert_eq!(Puzzle::from_page("<html></html>"), Puzzle::default());
+    }
+
+    #[test]
+    fn test_module_source() {
+        let puzzle = Puzzle {
+            title: Some("Mull It Over".to_owned()),
+            example: Some("mul(2,4)\n\"do()\"\n".to_owned()),
+        };
+        let source = module_source(2024, 3, &puzzle);
+        assert!(source.starts_with("//! # Day 3: Mull It Over\n"));
+        assert!(source.contains("//! [puzzle site](https://adventofcode.com/2024/day/3)\n"));
+        assert!(source.contains("pub fn part2(_input: &crate::PuzzleInput)"));
+        assert!(
+            source
+                .contains("crate::register!(2024, 3, \"Mull It Over\", 1 => part1, 2 => part2);\n")
+        );
+        assert!(source.contains(concat!(
+            "    const INPUT: &str = concat!(\n",
+            "        \"mul(2,4)\\n\",\n",
+            "        \"\\\"do()\\\"\"\n",
+            "    );\n",
+        )));
+        assert!(source.ends_with("    fn test_part2() {\n        assert_eq!(&super::part2(&INPUT.into()).unwrap(), \"TODO\");\n    }\n}\n"));
+
+        let source = module_source(2025, 10, &Puzzle::default());
+        assert!(source.starts_with("//! # Day 10: TODO\n"));
+        assert!(source.contains("    const INPUT: &str = \"\";\n"));
+    }
+
+    #[test]
+    fn test_declare_module() {
+        let lib = declare_module(LIB, 2024, 2).unwrap();
+        assert!(lib.contains("    pub mod day01;\n    pub mod day02;\n    pub mod day03;\n}\n"));
+        let lib = declare_module(LIB, 2024, 10).unwrap();
+        assert!(lib.contains("    pub mod day03;\n    pub mod day10;\n}\n"));
+        assert!(declare_module(LIB, 2024, 3).is_err());
+
+        let lib = declare_module(LIB, 2025, 1).unwrap();
+        assert!(lib.contains(concat!(
+            "    pub mod day03;\n",
+            "}\n",
+            "\n",
+            "/// # Solutions for 2025\n",
+            "///\n",
+            "/// [puzzle site](https://adventofcode.com/2025)\n",
+            "pub mod aoc2025 {\n",
+            "    pub mod day01;\n",
+            "}\n",
+            "\n",
+            "/// Common return type",
+        )));
+    }
+}
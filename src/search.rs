@@ -0,0 +1,343 @@
+//! Shortest paths in implicit graphs
+//!
+//! A graph is given by its start states and a function returning the
+//! neighbors of a state, so states can be anything hashable like grid
+//! positions or positions combined with a direction. Searches stop once the
+//! goal is reached, after settling every other goal state of the same cost,
+//! or explore everything reachable if no state is a goal.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Outcome of a search holding the distances and the predecessor graph of all
+/// states settled
+pub struct ShortestPaths<S, C> {
+    distances: HashMap<S, C>,
+    predecessors: HashMap<S, Vec<S>>,
+    goals: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> ShortestPaths<S, C> {
+    /// Returns the distance from the nearest start to a state if it was reached
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    /// Returns the distances of all states reached
+    pub fn distances(&self) -> &HashMap<S, C> {
+        &self.distances
+    }
+
+    /// Returns the goal states reached at the lowest cost
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    /// Returns the lowest cost of reaching a goal unless none was reached
+    pub fn goal_distance(&self) -> Option<C> {
+        self.distance(self.goals.first()?)
+    }
+
+    /// Returns the states preceding a state on its shortest paths, which is
+    /// empty for start states
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns one shortest path from a start to a state including both ends
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        self.distances.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(predecessor) = self.predecessors(path.last().unwrap()).first() {
+            path.push(predecessor.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns every shortest path from a start to a state including both ends
+    ///
+    /// The number of paths can grow exponentially with their length, so
+    /// [`ShortestPaths::on_shortest_paths`] is preferable if only the states
+    /// matter.
+    pub fn all_paths(&self, state: &S) -> Vec<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return Vec::new();
+        }
+        let predecessors = self.predecessors(state);
+        if predecessors.is_empty() {
+            return vec![vec![state.clone()]];
+        }
+        let mut paths = Vec::new();
+        for predecessor in predecessors {
+            for mut path in self.all_paths(predecessor) {
+                path.push(state.clone());
+                paths.push(path);
+            }
+        }
+        paths
+    }
+
+    /// Returns all states on any shortest path to any of the given states
+    pub fn on_shortest_paths<'a>(&self, states: impl IntoIterator<Item = &'a S>) -> HashSet<S>
+    where
+        S: 'a,
+    {
+        let mut visited = HashSet::new();
+        let mut to_visit: Vec<S> = (states.into_iter())
+            .filter(|state| self.distances.contains_key(state))
+            .cloned()
+            .collect();
+        while let Some(state) = to_visit.pop() {
+            if visited.insert(state.clone()) {
+                to_visit.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+        visited
+    }
+}
+
+/// Entry of the priority queue ordered such that the lowest priority is
+/// popped first
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+/// Finds shortest paths with non-negative costs of moving to a neighbor
+///
+/// The neighbors function returns each neighbor with the cost of the step and
+/// `C::default()` is taken to be zero.
+pub fn dijkstra<S, C, N>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> N,
+    is_goal: impl FnMut(&S) -> bool,
+) -> ShortestPaths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Default + Ord + Add<Output = C>,
+    N: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), is_goal)
+}
+
+/// Finds shortest paths like [`dijkstra`] but visits states in the order of
+/// their cost plus a heuristic estimate of the remaining cost to a goal
+///
+/// The heuristic must never overestimate and must not decrease by more than
+/// the cost of a step for the distances to be exact.
+pub fn astar<S, C, N>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> N,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> ShortestPaths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Default + Ord + Add<Output = C>,
+    N: IntoIterator<Item = (S, C)>,
+{
+    let mut distances = HashMap::new();
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::new();
+    let mut settled = HashSet::new();
+    let mut start_states = HashSet::new();
+    let mut goals = Vec::new();
+    let mut queue = BinaryHeap::new();
+    for state in starts {
+        start_states.insert(state.clone());
+        distances.insert(state.clone(), C::default());
+        let priority = heuristic(&state);
+        queue.push(Entry {
+            priority,
+            cost: C::default(),
+            state,
+        });
+    }
+    while let Some(Entry { cost, state, .. }) = queue.pop() {
+        if cost > distances[&state] || !settled.insert(state.clone()) {
+            continue;
+        }
+        if let Some(goal) = goals.first()
+            && cost > distances[goal]
+        {
+            break;
+        }
+        if is_goal(&state) {
+            goals.push(state.clone());
+            continue;
+        }
+        if !goals.is_empty() {
+            continue;
+        }
+        for (neighbor, step) in neighbors(&state) {
+            // Zero-cost steps back to settled states would make cycles of
+            // predecessors
+            if settled.contains(&neighbor) || start_states.contains(&neighbor) {
+                continue;
+            }
+            let neighbor_cost = cost + step;
+            match distances.get(&neighbor) {
+                Some(&old) if neighbor_cost > old => continue,
+                Some(&old) if neighbor_cost == old => {
+                    predecessors
+                        .entry(neighbor)
+                        .or_default()
+                        .push(state.clone());
+                    continue;
+                }
+                _ => (),
+            }
+            distances.insert(neighbor.clone(), neighbor_cost);
+            predecessors.insert(neighbor.clone(), vec![state.clone()]);
+            queue.push(Entry {
+                priority: neighbor_cost + heuristic(&neighbor),
+                cost: neighbor_cost,
+                state: neighbor,
+            });
+        }
+    }
+    // Drop states still waiting in the queue since their distances may be too high
+    distances.retain(|state, _| settled.contains(state));
+    predecessors.retain(|state, _| settled.contains(state));
+    ShortestPaths {
+        distances,
+        predecessors,
+        goals,
+    }
+}
+
+/// Finds shortest paths counting steps in a breadth-first search
+pub fn bfs<S, N>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> N,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> ShortestPaths<S, usize>
+where
+    S: Clone + Eq + Hash,
+    N: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::new();
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::new();
+    let mut goals = Vec::new();
+    let mut queue = VecDeque::new();
+    for state in starts {
+        if !distances.contains_key(&state) {
+            distances.insert(state.clone(), 0);
+            queue.push_back(state);
+        }
+    }
+    while let Some(state) = queue.pop_front() {
+        let distance = distances[&state];
+        if let Some(goal) = goals.first()
+            && distance > distances[goal]
+        {
+            break;
+        }
+        if is_goal(&state) {
+            goals.push(state);
+            continue;
+        }
+        if !goals.is_empty() {
+            continue;
+        }
+        for neighbor in neighbors(&state) {
+            match distances.get(&neighbor) {
+                None => {
+                    distances.insert(neighbor.clone(), distance + 1);
+                    predecessors.insert(neighbor.clone(), vec![state.clone()]);
+                    queue.push_back(neighbor);
+                }
+                Some(&other) if other == distance + 1 => {
+                    predecessors
+                        .entry(neighbor)
+                        .or_default()
+                        .push(state.clone());
+                }
+                Some(_) => (),
+            }
+        }
+    }
+    ShortestPaths {
+        distances,
+        predecessors,
+        goals,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, bfs, dijkstra};
+
+    /// Ring of ten states where stepping forward costs 1 and jumping ahead by
+    /// three costs 2
+    fn ring(state: &u8) -> [(u8, u32); 2] {
+        [((state + 1) % 10, 1), ((state + 3) % 10, 2)]
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let paths = dijkstra([0], ring, |&state| state == 7);
+        assert_eq!(paths.goal_distance(), Some(5));
+        assert_eq!(paths.goals(), [7]);
+        let mut all_paths = paths.all_paths(&7);
+        all_paths.sort();
+        assert_eq!(
+            all_paths,
+            [vec![0, 1, 4, 7], vec![0, 3, 4, 7], vec![0, 3, 6, 7]]
+        );
+        assert_eq!(paths.path(&7).map(|path| path.len()), Some(4));
+        assert_eq!(paths.on_shortest_paths([&7]).len(), 6);
+
+        let paths = dijkstra([0], ring, |_| false);
+        assert_eq!(paths.distances().len(), 10);
+        assert_eq!(paths.distance(&9), Some(6));
+        assert_eq!(paths.goal_distance(), None);
+
+        // Zero-cost steps back and forth between two states
+        let back_and_forth = |&state: &u8| [(1 - state, 0u32)];
+        let paths = dijkstra([0], back_and_forth, |&state| state == 9);
+        assert_eq!(paths.path(&1), Some(vec![0, 1]));
+        let paths = dijkstra([0, 1], back_and_forth, |_| false);
+        assert_eq!(paths.predecessors(&0), []);
+        assert_eq!(paths.predecessors(&1), []);
+    }
+
+    #[test]
+    fn test_astar() {
+        let heuristic = |&state: &u8| 7u32.saturating_sub(state as u32).div_ceil(3);
+        let paths = astar([0], ring, heuristic, |&state| state == 7);
+        assert_eq!(paths.goal_distance(), Some(5));
+    }
+
+    #[test]
+    fn test_bfs() {
+        let neighbors = |&state: &u8| [(state + 1) % 10, (state + 3) % 10];
+        let paths = bfs([0, 5], neighbors, |&state| state % 10 == 9);
+        assert_eq!(paths.goal_distance(), Some(2));
+        assert_eq!(paths.goals(), [9]);
+        assert_eq!(paths.all_paths(&9), [vec![5, 6, 9], vec![5, 8, 9]]);
+    }
+}
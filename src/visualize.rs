@@ -0,0 +1,229 @@
+//! Frames showing how simulations evolve
+//!
+//! Solutions simulating something visual emit a frame for every state they go
+//! through to an implementation of [`Visualize`]. Frames are drawn lazily, so
+//! simulating with the no-op visualization `()` costs nothing. A [`Player`]
+//! shows the frames in the terminal as they arrive while a [`Recording`] keeps
+//! them for later.
+
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, queue, style, terminal};
+
+use crate::Grid;
+
+/// Picture of a simulation's state with a caption describing it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    /// State drawn with one character per cell
+    pub picture: Grid<char>,
+    /// Short description like the number of steps taken
+    pub caption: String,
+}
+
+impl Frame {
+    /// Returns a frame of a picture with a caption
+    pub fn new(picture: Grid<char>, caption: impl Into<String>) -> Self {
+        Self {
+            picture,
+            caption: caption.into(),
+        }
+    }
+}
+
+/// Receiver of the frames a simulation emits
+pub trait Visualize {
+    /// Receives the next frame which is drawn by calling `draw` only if the
+    /// frame is actually used
+    fn frame(&mut self, draw: &dyn Fn() -> Frame);
+}
+
+/// Visualization ignoring all frames
+impl Visualize for () {
+    fn frame(&mut self, _draw: &dyn Fn() -> Frame) {}
+}
+
+/// Visualization keeping all frames
+#[derive(Clone, Debug, Default)]
+pub struct Recording {
+    frames: Vec<Frame>,
+}
+
+impl Recording {
+    /// Returns a recording without frames
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the recorded frames in the order they were emitted
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+}
+
+impl Visualize for Recording {
+    fn frame(&mut self, draw: &dyn Fn() -> Frame) {
+        self.frames.push(draw());
+    }
+}
+
+/// Slowest and fastest speeds in frames per second
+const SPEED_RANGE: (f64, f64) = (0.25, 1000.0);
+
+const PLAYER_HELP: &str = "space pause · ←/→ slower/faster · n next frame · q skip to the end";
+
+/// Visualization playing frames in the terminal at an adjustable speed
+///
+/// Each frame replaces the previous one on the screen. When the standard
+/// input is a terminal, keys control the player while frames are shown: space
+/// pauses, the arrow keys change the speed, `n` advances a paused player by
+/// one frame, and `q` skips all remaining frames.
+pub struct Player {
+    frames_per_second: f64,
+    interactive: bool,
+    paused: bool,
+    skipping: bool,
+    frame_count: usize,
+}
+
+impl Player {
+    /// Returns a player showing a certain number of frames per second
+    pub fn new(frames_per_second: f64) -> Self {
+        let interactive = std::io::stdin().is_terminal() && terminal::enable_raw_mode().is_ok();
+        Self {
+            frames_per_second: frames_per_second.clamp(SPEED_RANGE.0, SPEED_RANGE.1),
+            interactive,
+            paused: false,
+            skipping: false,
+            frame_count: 0,
+        }
+    }
+
+    /// Draws a frame over the previous one
+    fn draw(&self, frame: &Frame) -> std::io::Result<()> {
+        let mut stdout = std::io::stdout().lock();
+        if self.frame_count == 1 {
+            queue!(stdout, terminal::Clear(terminal::ClearType::All))?;
+        }
+        queue!(stdout, cursor::MoveTo(0, 0))?;
+        for row in frame.picture.rows() {
+            let row: String = row.iter().collect();
+            queue!(stdout, style::Print(row), cursor::MoveToNextLine(1))?;
+        }
+        let status = format!(
+            "Frame {}: {} ({} fps{})",
+            self.frame_count,
+            frame.caption,
+            self.frames_per_second,
+            if self.paused { ", paused" } else { "" }
+        );
+        queue!(stdout, style::Print(status), cursor::MoveToNextLine(1))?;
+        if self.interactive {
+            queue!(stdout, style::Print(PLAYER_HELP), cursor::MoveToNextLine(1))?;
+        }
+        queue!(stdout, terminal::Clear(terminal::ClearType::FromCursorDown))?;
+        stdout.flush()
+    }
+
+    /// Reacts to a key and returns whether the frame should be drawn again
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let (slowest, fastest) = SPEED_RANGE;
+        match key.code {
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Left | KeyCode::Char('-') => {
+                self.frames_per_second = (self.frames_per_second / 2.0).max(slowest)
+            }
+            KeyCode::Right | KeyCode::Char('+') => {
+                self.frames_per_second = (self.frames_per_second * 2.0).min(fastest)
+            }
+            KeyCode::Char('q') | KeyCode::Esc => self.skipping = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.skipping = true
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Waits until the next frame is due while handling keys
+    fn wait(&mut self, frame: &Frame) -> std::io::Result<()> {
+        let shown = Instant::now();
+        loop {
+            let delay = Duration::from_secs_f64(1.0 / self.frames_per_second);
+            let elapsed = shown.elapsed();
+            if !self.paused && elapsed >= delay {
+                return Ok(());
+            }
+            if !self.interactive {
+                std::thread::sleep(delay - elapsed);
+                continue;
+            }
+            let timeout = if self.paused { delay } else { delay - elapsed };
+            if !event::poll(timeout)? {
+                continue;
+            }
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                if key.code == KeyCode::Char('n') && self.paused {
+                    return Ok(());
+                }
+                if self.handle_key(key) {
+                    if self.skipping {
+                        return Ok(());
+                    }
+                    self.draw(frame)?;
+                }
+            }
+        }
+    }
+}
+
+impl Visualize for Player {
+    fn frame(&mut self, draw: &dyn Fn() -> Frame) {
+        if self.skipping {
+            return;
+        }
+        let frame = draw();
+        self.frame_count += 1;
+        // Failing to write to the terminal only means the frame isn't seen
+        let _ = self.draw(&frame).and_then(|()| self.wait(&frame));
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        if self.interactive {
+            let _ = terminal::disable_raw_mode();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frame, Recording, Visualize};
+    use crate::Grid;
+
+    #[test]
+    fn test_recording() {
+        let mut recording = Recording::new();
+        for step in 0..3 {
+            let mut picture = Grid::new(3, 1, '.');
+            picture[[0, step]] = '#';
+            recording.frame(&|| Frame::new(picture.clone(), format!("step {step}")));
+        }
+        let frames = recording.frames();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[2].picture.to_string(), "..#");
+        assert_eq!(frames[1].caption, "step 1");
+
+        let drawn = std::cell::Cell::new(false);
+        ().frame(&|| {
+            drawn.set(true);
+            Frame::new(Grid::new(0, 0, ' '), "")
+        });
+        assert!(!drawn.get());
+    }
+}
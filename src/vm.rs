@@ -0,0 +1,273 @@
+//! Register machines running programs of instructions
+//!
+//! A puzzle defines its instruction set as a type implementing [`Instruction`]
+//! which tells how long each instruction takes and how it changes the
+//! registers. [`Machine`] runs the fetch and execute loop, keeps track of the
+//! instruction pointer and elapsed cycles, and collects the output.
+
+use std::ops::{Index, IndexMut};
+
+use crate::PuzzleError;
+
+/// What happens after an instruction was executed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Effect<O> {
+    /// Continue with the next instruction
+    Next,
+    /// Write a value to the output and continue with the next instruction
+    Output(O),
+    /// Continue at an absolute instruction index
+    Jump(usize),
+    /// Continue at an instruction index relative to the current one
+    JumpBy(isize),
+    /// Stop the machine
+    Halt,
+}
+
+/// Instruction of an instruction set
+pub trait Instruction {
+    /// Registers the instructions operate on
+    type Registers;
+    /// Values written to the output
+    type Output;
+
+    /// Returns the number of cycles the instruction takes
+    fn cycles(&self) -> usize {
+        1
+    }
+
+    /// Changes the registers as the instruction says
+    fn execute(&self, registers: &mut Self::Registers) -> Effect<Self::Output>;
+}
+
+/// Fixed number of registers addressed by their index
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Registers<T, const N: usize>(pub [T; N]);
+
+impl<T, const N: usize> Index<usize> for Registers<T, N> {
+    type Output = T;
+
+    fn index(&self, register: usize) -> &T {
+        &self.0[register]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Registers<T, N> {
+    fn index_mut(&mut self, register: usize) -> &mut T {
+        &mut self.0[register]
+    }
+}
+
+/// Machine running a program until the instruction pointer leaves it or an
+/// instruction halts
+#[derive(Clone, Debug)]
+pub struct Machine<I: Instruction> {
+    program: Vec<I>,
+    registers: I::Registers,
+    pointer: usize,
+    halted: bool,
+    cycle: usize,
+    steps: usize,
+    step_limit: Option<usize>,
+    output: Vec<I::Output>,
+}
+
+impl<I: Instruction> Machine<I> {
+    /// Returns a machine about to run the first instruction of a program
+    pub fn new(program: Vec<I>, registers: I::Registers) -> Self {
+        Self {
+            program,
+            registers,
+            pointer: 0,
+            halted: false,
+            cycle: 0,
+            steps: 0,
+            step_limit: None,
+            output: Vec::new(),
+        }
+    }
+
+    /// Returns the machine failing to run once it has executed a number of
+    /// instructions without halting
+    pub fn with_step_limit(mut self, limit: usize) -> Self {
+        self.step_limit = Some(limit);
+        self
+    }
+
+    /// Returns the program
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    /// Returns the registers
+    pub fn registers(&self) -> &I::Registers {
+        &self.registers
+    }
+
+    /// Returns the mutable registers
+    pub fn registers_mut(&mut self) -> &mut I::Registers {
+        &mut self.registers
+    }
+
+    /// Returns the index of the next instruction
+    pub fn pointer(&self) -> usize {
+        self.pointer
+    }
+
+    /// Returns the number of the current cycle starting at 1, or 0 before the
+    /// first instruction started
+    pub fn cycle(&self) -> usize {
+        self.cycle
+    }
+
+    /// Returns the number of instructions executed
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns the values written so far
+    pub fn output(&self) -> &[I::Output] {
+        &self.output
+    }
+
+    /// Returns whether the machine stopped
+    pub fn is_halted(&self) -> bool {
+        self.halted || self.pointer >= self.program.len()
+    }
+
+    /// Executes the next instruction unless the machine stopped
+    ///
+    /// The hook is called during each cycle the instruction takes, before the
+    /// instruction changes the registers at the end of its last cycle.
+    pub fn step(&mut self, mut hook: impl FnMut(&Self)) {
+        if self.is_halted() {
+            return;
+        }
+        for _ in 0..self.program[self.pointer].cycles() {
+            self.cycle += 1;
+            hook(self);
+        }
+        let effect = self.program[self.pointer].execute(&mut self.registers);
+        self.steps += 1;
+        match effect {
+            Effect::Next => self.pointer += 1,
+            Effect::Output(value) => {
+                self.output.push(value);
+                self.pointer += 1;
+            }
+            Effect::Jump(target) => self.pointer = target,
+            Effect::JumpBy(offset) => match self.pointer.checked_add_signed(offset) {
+                Some(target) => self.pointer = target,
+                None => self.halted = true,
+            },
+            Effect::Halt => self.halted = true,
+        }
+    }
+
+    /// Runs until the machine stops and returns the output
+    pub fn run(&mut self) -> Result<&[I::Output], PuzzleError> {
+        self.run_with_hook(|_| ())
+    }
+
+    /// Runs until the machine stops calling the hook during each cycle and
+    /// returns the output
+    pub fn run_with_hook(
+        &mut self,
+        mut hook: impl FnMut(&Self),
+    ) -> Result<&[I::Output], PuzzleError> {
+        while !self.is_halted() {
+            self.check_step_limit()?;
+            self.step(&mut hook);
+        }
+        Ok(&self.output)
+    }
+
+    /// Runs until the machine stops or a condition checked after each
+    /// instruction holds
+    ///
+    /// At least one instruction is executed unless the machine stopped, so
+    /// running again continues past a position the condition stopped at.
+    pub fn run_until(
+        &mut self,
+        mut condition: impl FnMut(&Self) -> bool,
+    ) -> Result<(), PuzzleError> {
+        while !self.is_halted() {
+            self.check_step_limit()?;
+            self.step(|_| ());
+            if condition(self) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_step_limit(&self) -> Result<(), PuzzleError> {
+        match self.step_limit {
+            Some(limit) if self.steps >= limit => Err(PuzzleError::NoSolution(format!(
+                "program still running after {limit} steps"
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Effect, Instruction, Machine, Registers};
+
+    /// Counter machine with one register
+    enum Counter {
+        Decrement,
+        Print,
+        JumpIfPositive(isize),
+    }
+
+    impl Instruction for Counter {
+        type Registers = Registers<i32, 1>;
+        type Output = i32;
+
+        fn cycles(&self) -> usize {
+            if let Counter::Print = self { 2 } else { 1 }
+        }
+
+        fn execute(&self, registers: &mut Self::Registers) -> Effect<i32> {
+            match self {
+                Counter::Decrement => registers[0] -= 1,
+                Counter::Print => return Effect::Output(registers[0]),
+                Counter::JumpIfPositive(_) if registers[0] <= 0 => (),
+                &Counter::JumpIfPositive(offset) => return Effect::JumpBy(offset),
+            }
+            Effect::Next
+        }
+    }
+
+    #[test]
+    fn test_machine() {
+        use Counter::*;
+        let program = vec![Decrement, Print, JumpIfPositive(-2)];
+        let mut machine = Machine::new(program, Registers([3]));
+        let mut cycles = Vec::new();
+        let output = machine.run_with_hook(|machine| cycles.push(machine.cycle()));
+        assert_eq!(output.unwrap(), [2, 1, 0]);
+        assert_eq!(
+            (machine.steps(), machine.cycle(), machine.pointer()),
+            (9, 12, 3)
+        );
+        assert_eq!(cycles, (1..=12).collect::<Vec<_>>());
+
+        let program = vec![Decrement, Print, JumpIfPositive(-2)];
+        let mut machine = Machine::new(program, Registers([3]));
+        let mut pointers = Vec::new();
+        let at_print = |machine: &Machine<Counter>| machine.pointer() == 1;
+        while !machine.is_halted() {
+            machine.run_until(at_print).unwrap();
+            pointers.push((machine.pointer(), machine.registers()[0]));
+        }
+        assert_eq!(pointers, [(1, 2), (1, 1), (1, 0), (3, 0)]);
+
+        let program = vec![Decrement, JumpIfPositive(-1)];
+        let mut machine = Machine::new(program, Registers([i32::MAX])).with_step_limit(100);
+        assert!(machine.run().is_err());
+        assert_eq!(machine.registers()[0], i32::MAX - 50);
+    }
+}